@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use mcts_rs::{uct, PerChild, Process, Mcts, SelectResult, State};
+use mcts_rs::{uct, with_search_rng, PerChild, Process, Mcts, SelectResult, State};
 use ordered_float::OrderedFloat;
 use rand::{prelude::SliceRandom, Rng, thread_rng};
 use smallvec::SmallVec;
@@ -160,12 +160,12 @@ impl Process for SticksProcess {
             if unexplored_moves.is_empty() || best_edge.uct.uct(total_visits) > uct::State::baseline(total_visits) {
                 SelectResult::Existing(best_edge.key())
             } else {
-                unexplored_moves.choose(&mut thread_rng()).map(|&n| Self::PerChild::new(n))
+                with_search_rng(|mut rng| unexplored_moves.choose(&mut rng).map(|&n| Self::PerChild::new(n)))
                     .map(|per_child| SelectResult::Add(per_child))
                     .unwrap_or(SelectResult::None)
             }
         } else {
-            unexplored_moves.choose(&mut thread_rng()).map(|&n| Self::PerChild::new(n))
+            with_search_rng(|mut rng| unexplored_moves.choose(&mut rng).map(|&n| Self::PerChild::new(n)))
                 .map(|per_child| SelectResult::Add(per_child))
                 .unwrap_or(SelectResult::None)
         }
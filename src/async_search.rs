@@ -0,0 +1,90 @@
+use crate::{mcts::Mcts, node::Node, pending::PendingPolicy, probe_status::ProbeStatus, process::Process, trace::Trace};
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::future::Future;
+
+/// A driver for searches where every probe is evaluated asynchronously, such
+/// as by a remote service. The search is itself a future, so it runs on
+/// whatever executor awaits it, while at most `max_pending` evaluations are
+/// in flight at any time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AsyncSearch {
+    max_pending: usize
+}
+
+impl AsyncSearch {
+    /// Returns a driver that keeps at most `max_pending` evaluations in
+    /// flight.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_pending` - the maximum number of pending evaluations
+    ///
+    pub fn new(max_pending: usize) -> Self {
+        Self { max_pending: max_pending.max(1) }
+    }
+
+    /// Returns the maximum number of pending evaluations.
+    pub fn max_pending(&self) -> usize {
+        self.max_pending
+    }
+
+    /// Search `search_tree` with `num_probes` probes. Every trace from
+    /// `probe` is given to `prepare`, which returns the input of `evaluate` or
+    /// `None` to skip it. The output of `evaluate` is the state to add at the
+    /// end of the trace, if any, and the update to apply. A new probe is
+    /// only made when fewer than `max_pending` evaluations are in flight, and
+    /// no more probes are made once the search tree has been stopped. Returns
+    /// the status of every probe in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_tree` - the search tree to search
+    /// * `num_probes` - the number of probes to make
+    /// * `prepare` - returns the input of `evaluate` for a trace
+    /// * `evaluate` - starts the evaluation of a single probe
+    ///
+    /// # Panics
+    ///
+    /// If the pending policy of `search_tree` is `PendingPolicy::Wait`, since
+    /// a probe would block the executor while it waits for an evaluation that
+    /// can only finish once this search is polled again.
+    ///
+    pub async fn search<'a, P, I, F>(
+        &self,
+        search_tree: &'a Mcts<P>,
+        num_probes: usize,
+        mut prepare: impl FnMut(&Trace<'a, P, Node<P>>) -> Option<I>,
+        mut evaluate: impl FnMut(I) -> F
+    ) -> Vec<ProbeStatus>
+        where P: Process, F: Future<Output=(Option<P::State>, P::Update)>
+    {
+        assert_ne!(search_tree.pending_policy(), PendingPolicy::Wait, "an asynchronous search cannot wait for pending edges");
+
+        let mut statuses = Vec::with_capacity(num_probes);
+        let mut pending = FuturesUnordered::new();
+        let mut is_cancelled = false;
+
+        loop {
+            if !is_cancelled && statuses.len() < num_probes && pending.len() < self.max_pending {
+                let (trace, status) = search_tree.probe();
+
+                statuses.push(status);
+                is_cancelled = status == ProbeStatus::Cancelled;
+
+                if is_cancelled {
+                    search_tree.cancel(trace, false);
+                } else if let Some(input) = prepare(&trace) {
+                    let evaluation = evaluate(input);
+
+                    pending.push(async move { (trace, evaluation.await) });
+                }
+            } else if let Some((trace, (state, update))) = pending.next().await {
+                search_tree.update(trace, state, update);
+            } else {
+                break
+            }
+        }
+
+        statuses
+    }
+}
@@ -0,0 +1,293 @@
+use goban::rules::{game::Game, Move, Player, CHINESE};
+use mcts_rs::{time::Clock, Mcts, Ponder};
+use std::{thread, time::Duration};
+
+use super::{goban::{search_once, GobanProcess, GobanState}, gtp::Vertex};
+
+/// How often the search is checked for whether the time is up.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The byo-yomi part of the time settings, as given by `time_settings`.
+#[derive(Clone, Copy, Debug)]
+struct TimeSettings {
+    byoyomi_time: Duration,
+    byoyomi_stones: u32
+}
+
+impl TimeSettings {
+    /// Returns the clock with `main_time` and `stones` remaining, or `None`
+    /// if there is no time limit, which GTP signals with a byo-yomi time but
+    /// no byo-yomi stones, whatever the main time.
+    fn clock(&self, main_time: Duration, stones: u32) -> Option<Clock> {
+        if self.byoyomi_stones == 0 && !self.byoyomi_time.is_zero() {
+            None
+        } else if stones > 0 {
+            Some(Clock::new(main_time).with_moves_to_go(stones))
+        } else if self.byoyomi_stones > 0 {
+            Some(Clock::new(main_time).with_byoyomi(self.byoyomi_time / self.byoyomi_stones, 1))
+        } else {
+            Some(Clock::new(main_time))
+        }
+    }
+}
+
+pub struct Engine {
+    size: usize,
+    komi: f32,
+    game: Game,
+    search_tree: Option<Mcts<GobanProcess>>,
+    num_threads: usize,
+    move_time: Duration,
+    time_settings: Option<TimeSettings>,
+    clocks: [Clock; 2],
+    is_verbose: bool
+}
+
+fn new_game(size: usize, komi: f32) -> Result<Game, String> {
+    Game::builder()
+        .size((size as u32, size as u32))
+        .rule(CHINESE)
+        .komi(komi)
+        .build()
+}
+
+fn index(player: Player) -> usize {
+    match player {
+        Player::Black => 0,
+        Player::White => 1
+    }
+}
+
+impl Engine {
+    pub fn new(num_threads: usize, move_time: Duration) -> Self {
+        let size = 9;
+        let komi = 7.0;
+        let game = new_game(size, komi).unwrap();
+        let search_tree = Some(Mcts::new(GobanProcess::new(), GobanState::new(game.clone())));
+        let clock = Clock::new(move_time).with_moves_to_go(1);
+
+        Self {
+            size, komi, game, search_tree, num_threads, move_time,
+            time_settings: None,
+            clocks: [clock.clone(), clock],
+            is_verbose: false
+        }
+    }
+
+    /// Returns this engine, which writes a summary of every search to stderr
+    /// if `is_verbose` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_verbose` - whether to write search summaries to stderr
+    ///
+    pub fn with_verbose(self, is_verbose: bool) -> Self {
+        Self { is_verbose, ..self }
+    }
+
+    fn default_clock(&self) -> Clock {
+        Clock::new(self.move_time).with_moves_to_go(1)
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    fn search_tree(&mut self) -> &mut Mcts<GobanProcess> {
+        self.search_tree.as_mut().unwrap()
+    }
+
+    fn reset(&mut self) {
+        let state = GobanState::new(self.game.clone());
+
+        self.search_tree().reset(state);
+    }
+
+    pub fn set_size(&mut self, size: usize) -> Result<(), String> {
+        self.game = new_game(size, self.komi)?;
+        self.size = size;
+        self.reset();
+        Ok(())
+    }
+
+    pub fn clear_board(&mut self) {
+        self.game = new_game(self.size, self.komi).unwrap();
+        self.reset();
+    }
+
+    pub fn set_komi(&mut self, komi: f32) {
+        self.komi = komi;
+        self.game.set_komi(komi);
+        self.reset();
+    }
+
+    pub fn set_time_settings(&mut self, main_time: Duration, byoyomi_time: Duration, byoyomi_stones: u32) {
+        let time_settings = TimeSettings { byoyomi_time, byoyomi_stones };
+        let clock = time_settings.clock(main_time, 0).unwrap_or_else(|| self.default_clock());
+
+        self.time_settings = Some(time_settings);
+        self.clocks = [clock.clone(), clock];
+    }
+
+    pub fn set_time_left(&mut self, player: Player, time_left: Duration, stones: u32) {
+        if let Some(time_settings) = self.time_settings {
+            self.clocks[index(player)] = time_settings.clock(time_left, stones).unwrap_or_else(|| self.default_clock());
+        }
+    }
+
+    fn pass(&mut self) {
+        if self.game.is_over() {
+            self.game.resume();
+        }
+
+        self.game.play(Move::Pass);
+        self.reset();
+    }
+
+    /// Returns a copy of the game where it is `player`'s turn. The goban
+    /// crate can only hand the turn over with a pass, so the pass counter is
+    /// reset afterwards and that pass never counts towards the end of the
+    /// game.
+    fn game_for(&self, player: Player) -> Game {
+        let mut game = self.game.clone();
+
+        if game.is_over() {
+            game.resume();
+        }
+
+        if game.turn() != player {
+            game.play(Move::Pass);
+            game.resume();
+        }
+
+        game
+    }
+
+    pub fn play(&mut self, player: Player, vertex: Vertex) -> Result<(), String> {
+        let is_in_turn = self.game.turn() == player;
+        let mut game = self.game_for(player);
+
+        match vertex {
+            Vertex::Pass => { game.play(Move::Pass); },
+            Vertex::Play(point) => {
+                game.try_play(Move::Play(point.0, point.1)).map_err(|_| "illegal move".to_string())?;
+            }
+        }
+
+        self.game = game;
+
+        match vertex {
+            Vertex::Play(point) if is_in_turn && self.search_tree().reroot(point) => {},
+            _ => self.reset()
+        }
+
+        Ok(())
+    }
+
+    pub fn genmove(&mut self, player: Player) -> Vertex {
+        if self.game.turn() != player {
+            self.game = self.game_for(player);
+            self.reset();
+        }
+
+        let mut budget = self.clocks[index(player)].budget();
+        let ponder = Ponder::start(self.search_tree.take().unwrap(), self.num_threads, search_once);
+
+        while !budget.is_expired() {
+            thread::sleep(POLL_INTERVAL.min(budget.remaining()));
+            budget.observe(ponder.search_tree());
+        }
+
+        let search_tree = ponder.stop();
+        let best = search_tree.path().next().map(|step| {
+            let (visits, win_rate) = step.map(|_, per_child| (per_child.visits(), per_child.win_rate()));
+
+            if self.is_verbose {
+                eprintln!(
+                    "{} visits, {} nodes, {} win rate {:.1}%",
+                    search_tree.root().total_visits(),
+                    search_tree.len(),
+                    Vertex::Play(step.key()).to_string(self.size),
+                    100.0 * win_rate
+                );
+            }
+
+            (step.key(), visits)
+        });
+
+        self.search_tree = Some(search_tree);
+
+        if let Some((point, _)) = best.filter(|&(_, visits)| visits > 0) {
+            if self.play(player, Vertex::Play(point)).is_ok() {
+                return Vertex::Play(point);
+            }
+        }
+
+        self.pass();
+        Vertex::Pass
+    }
+
+    pub fn showboard(&self) -> String {
+        format!("\n{}", self.game.goban().pretty_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use goban::pieces::stones::Color;
+    use super::*;
+
+    fn engine() -> Engine {
+        Engine::new(1, Duration::from_millis(20))
+    }
+
+    #[test]
+    fn play_out_of_turn_does_not_pass() {
+        let mut engine = engine();
+
+        assert_eq!(engine.play(Player::White, Vertex::Play((4, 4))), Ok(()));
+        assert_eq!(engine.game.goban().get_stone((4, 4)), Color::White);
+        assert_eq!(engine.game.turn(), Player::Black);
+        assert_eq!(engine.game.passes(), 0);
+    }
+
+    #[test]
+    fn pass_out_of_turn_counts_once() {
+        let mut engine = engine();
+
+        assert_eq!(engine.play(Player::White, Vertex::Pass), Ok(()));
+        assert_eq!(engine.game.turn(), Player::Black);
+        assert_eq!(engine.game.passes(), 1);
+    }
+
+    #[test]
+    fn illegal_move_leaves_game_untouched() {
+        let mut engine = engine();
+
+        assert_eq!(engine.play(Player::Black, Vertex::Play((4, 4))), Ok(()));
+        assert!(engine.play(Player::Black, Vertex::Play((4, 4))).is_err());
+        assert_eq!(engine.game.turn(), Player::White);
+        assert_eq!(engine.game.passes(), 0);
+    }
+
+    #[test]
+    fn byoyomi_without_stones_has_no_time_limit() {
+        let time_settings = TimeSettings { byoyomi_time: Duration::from_secs(30), byoyomi_stones: 0 };
+
+        assert!(time_settings.clock(Duration::from_secs(600), 0).is_none());
+    }
+
+    #[test]
+    fn genmove_plays_for_the_given_player() {
+        let mut engine = engine();
+
+        assert_eq!(engine.play(Player::Black, Vertex::Play((4, 4))), Ok(()));
+
+        match engine.genmove(Player::Black) {
+            Vertex::Play(point) => { assert_eq!(engine.game.goban().get_stone(point), Color::Black) },
+            Vertex::Pass => { assert_eq!(engine.game.passes(), 1) }
+        }
+
+        assert_eq!(engine.game.turn(), Player::White);
+    }
+}
@@ -0,0 +1,197 @@
+use goban::{pieces::{stones::Stone, util::coord::Point}, rules::{game::Game, Move, Player}};
+use mcts_rs::{uct, Mcts, PerChild, ProbeStatus, Process, SelectResult, State};
+use ordered_float::OrderedFloat;
+use rand::{thread_rng, prelude::*};
+
+/// The number of visits a node needs before its children are expanded.
+const EXPAND_THRESHOLD: u32 = 32;
+
+pub struct GobanEdge {
+    point: Point,
+    uct: uct::PerChild,
+}
+
+impl PerChild for GobanEdge {
+    type Key = Point;
+
+    fn key(&self) -> Self::Key {
+        self.point
+    }
+}
+
+impl GobanEdge {
+    fn new(point: Point) -> Self {
+        Self { point, uct: uct::PerChild::new() }
+    }
+
+    pub fn visits(&self) -> u32 {
+        self.uct.visits()
+    }
+
+    pub fn win_rate(&self) -> f32 {
+        self.uct.win_rate(self.uct.total_value(), self.uct.visits())
+    }
+}
+
+pub struct GobanState {
+    goban: Game,
+    uct: uct::State
+}
+
+impl State for GobanState {
+    fn hash(&self) -> Option<u64> {
+        let hash = self.goban.goban().zobrist_hash();
+
+        if hash == 0 {
+            None
+        } else if self.turn() == Player::White {
+            Some(!hash)
+        } else {
+            Some(hash)
+        }
+    }
+}
+
+impl GobanState {
+    pub fn new(goban: Game) -> Self {
+        Self { goban, uct: uct::State::new() }
+    }
+
+    fn forward(&self, edge: &GobanEdge) -> Self {
+        let mut goban = self.goban.clone();
+        goban.play(Move::Play(edge.point.0, edge.point.1));
+
+        Self::new(goban)
+    }
+
+    pub fn total_visits(&self) -> u32 {
+        self.uct.visits()
+    }
+
+    fn turn(&self) -> Player {
+        self.goban.turn()
+    }
+
+    fn choose(goban: &Game, rng: &mut impl Rng) -> Option<Point> {
+        let color = goban.turn().stone_color();
+
+        goban.legals()
+            .filter(|&coordinates| !goban.check_eye(Stone { coordinates, color }))
+            .choose(rng)
+    }
+
+    /// Returns the outcome of a random game starting from this state, from
+    /// the perspective of the player to move.
+    fn evaluate(&self, rng: &mut impl Rng) -> f32 {
+        let (width, height) = self.goban.size();
+        let max_game_length = 2 * width * height;
+        let mut goban = self.goban.clone();
+        let mut count = 0;
+
+        while count < max_game_length && !goban.is_over() {
+            match Self::choose(&goban, rng) {
+                Some(point) => goban.play(Move::Play(point.0, point.1)),
+                None => goban.play(Move::Pass)
+            };
+
+            count += 1;
+        }
+
+        let (black, white) = goban.calculate_score();
+
+        if black == white {
+            0.5
+        } else {
+            ((black > white) == (self.turn() == Player::Black)) as i32 as f32
+        }
+    }
+}
+
+pub struct GobanUpdate {
+    player: Player,
+    uct: uct::Update
+}
+
+impl GobanUpdate {
+    fn new(player: Player, value: f32) -> Self {
+        Self { player, uct: uct::Update::new(value) }
+    }
+}
+
+pub struct GobanProcess;
+
+impl GobanProcess {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Process for GobanProcess {
+    type PerChild = GobanEdge;
+    type State = GobanState;
+    type Update = GobanUpdate;
+
+    fn best<'a>(&self, _: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> Option<<Self::PerChild as PerChild>::Key>
+        where Self::PerChild: 'a
+    {
+        edges.max_by_key(|per_child| per_child.visits()).map(|per_child| per_child.point)
+    }
+
+    fn select<'a>(&self, state: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild>
+        where Self::PerChild: 'a
+    {
+        let total_visits = state.uct.visits();
+
+        if let Some(best_edge) = edges.max_by_key(|edge| OrderedFloat(edge.uct.uct(total_visits))) {
+            if best_edge.uct.uct(total_visits) < uct::State::baseline(total_visits) {
+                match GobanState::choose(&state.goban, &mut thread_rng()).map(GobanEdge::new) {
+                    Some(new_edge) => SelectResult::Add(new_edge),
+                    None => SelectResult::Existing(best_edge.key())
+                }
+            } else {
+                SelectResult::Existing(best_edge.key())
+            }
+        } else {
+            match GobanState::choose(&state.goban, &mut thread_rng()).map(GobanEdge::new) {
+                Some(new_edge) => SelectResult::Add(new_edge),
+                None => SelectResult::None
+            }
+        }
+    }
+
+    fn update(&self, state: &Self::State, per_child: &Self::PerChild, update: &Self::Update, _: bool) {
+        state.uct.update();
+        per_child.uct.update(&uct::Update::new(
+            if update.player == state.turn() { update.uct.value() } else { 1.0 - update.uct.value() }
+        ));
+    }
+}
+
+/// Probe the given `search_tree` once, evaluate the resulting leaf with a
+/// random game, and update the search tree with the result.
+pub fn search_once(search_tree: &Mcts<GobanProcess>) {
+    match search_tree.probe() {
+        (trace, ProbeStatus::Cancelled) => {
+            search_tree.cancel(trace, false);
+        },
+        (trace, _) if trace.is_empty() => {
+            search_tree.root().uct.update();
+        },
+        (trace, _) => {
+            let last_step = trace.steps().last().unwrap();
+            let (next_state, total_visits) = last_step.map(|state, per_child| {
+                (state.forward(per_child), state.total_visits())
+            });
+            let update = GobanUpdate::new(
+                next_state.turn(),
+                next_state.evaluate(&mut thread_rng())
+            );
+
+            if total_visits > EXPAND_THRESHOLD {
+                search_tree.update(trace, Some(next_state), update);
+            } else {
+                search_tree.update(trace, None, update);
+            }
+        }
+    }
+}
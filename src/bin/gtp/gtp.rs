@@ -0,0 +1,183 @@
+use goban::{pieces::util::coord::Point, rules::Player};
+use std::fmt::{self, Display, Formatter};
+
+/// The column letters used by the Go Text Protocol, which skip `I`.
+const COLUMNS: &[u8] = b"ABCDEFGHJKLMNOPQRSTUVWXYZ";
+
+/// A single command, as received from the controller.
+#[derive(Debug, PartialEq)]
+pub struct Command<'a> {
+    id: Option<u32>,
+    name: &'a str,
+    args: Vec<&'a str>
+}
+
+impl<'a> Command<'a> {
+    /// Returns the command on the given `line`, or `None` if the line is
+    /// empty or only contains a comment.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - the line to parse
+    ///
+    pub fn parse(line: &'a str) -> Option<Self> {
+        let line = line.split('#').next().unwrap_or("");
+        let mut words = line.split_whitespace();
+        let first = words.next()?;
+
+        match first.parse::<u32>() {
+            Ok(id) => words.next().map(|name| Self { id: Some(id), name, args: words.collect() }),
+            Err(_) => Some(Self { id: None, name: first, args: words.collect() })
+        }
+    }
+
+    pub fn id(&self) -> Option<u32> {
+        self.id
+    }
+
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    pub fn args(&self) -> &[&'a str] {
+        &self.args
+    }
+}
+
+/// The response to a single command.
+#[derive(Debug, PartialEq)]
+pub enum Response {
+    Success(Option<u32>, String),
+    Failure(Option<u32>, String)
+}
+
+impl Display for Response {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        let (prefix, id, message) = match self {
+            Response::Success(id, message) => ('=', id, message),
+            Response::Failure(id, message) => ('?', id, message)
+        };
+
+        write!(fmt, "{}", prefix)?;
+        if let Some(id) = id {
+            write!(fmt, "{}", id)?;
+        }
+        if !message.is_empty() {
+            write!(fmt, " {}", message.trim_end())?;
+        }
+
+        write!(fmt, "\n\n")
+    }
+}
+
+/// A move as written in the Go Text Protocol.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Vertex {
+    Pass,
+    Play(Point)
+}
+
+impl Vertex {
+    /// Returns the vertex with the given `text` on a board of the given
+    /// `size`, where the top left corner is `(0, 0)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - the vertex, e.g. `D4` or `pass`
+    /// * `size` - the number of rows and columns on the board
+    ///
+    pub fn parse(text: &str, size: usize) -> Option<Self> {
+        if text.eq_ignore_ascii_case("pass") {
+            return Some(Vertex::Pass);
+        }
+
+        let text = text.to_ascii_uppercase();
+        let column = COLUMNS.iter().position(|&c| Some(c) == text.bytes().next())?;
+        let row = text.get(1..)?.parse::<usize>().ok()?;
+
+        if column < size && row >= 1 && row <= size {
+            Some(Vertex::Play(((size - row) as u8, column as u8)))
+        } else {
+            None
+        }
+    }
+
+    /// Returns this vertex as text on a board of the given `size`.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - the number of rows and columns on the board
+    ///
+    pub fn to_string(self, size: usize) -> String {
+        match self {
+            Vertex::Pass => "pass".to_string(),
+            Vertex::Play((row, column)) => {
+                format!("{}{}", COLUMNS[column as usize] as char, size - row as usize)
+            }
+        }
+    }
+}
+
+/// Returns the player with the given `text`, e.g. `b` or `white`.
+///
+/// # Arguments
+///
+/// * `text` - the color of the player
+///
+pub fn parse_player(text: &str) -> Option<Player> {
+    match text.to_ascii_lowercase().as_str() {
+        "b" | "black" => Some(Player::Black),
+        "w" | "white" => Some(Player::White),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_command_with_id() {
+        let command = Command::parse("12 play b D4").unwrap();
+
+        assert_eq!(command.id(), Some(12));
+        assert_eq!(command.name(), "play");
+        assert_eq!(command.args(), &["b", "D4"]);
+    }
+
+    #[test]
+    fn parse_command_without_id() {
+        let command = Command::parse("genmove w # comment").unwrap();
+
+        assert_eq!(command.id(), None);
+        assert_eq!(command.name(), "genmove");
+        assert_eq!(command.args(), &["w"]);
+    }
+
+    #[test]
+    fn parse_empty_command() {
+        assert_eq!(Command::parse("  # comment"), None);
+    }
+
+    #[test]
+    fn parse_vertex() {
+        assert_eq!(Vertex::parse("A1", 9), Some(Vertex::Play((8, 0))));
+        assert_eq!(Vertex::parse("j9", 9), Some(Vertex::Play((0, 8))));
+        assert_eq!(Vertex::parse("PASS", 9), Some(Vertex::Pass));
+        assert_eq!(Vertex::parse("K1", 9), None);
+        assert_eq!(Vertex::parse("A10", 9), None);
+    }
+
+    #[test]
+    fn vertex_to_string() {
+        assert_eq!(Vertex::Play((8, 0)).to_string(9), "A1");
+        assert_eq!(Vertex::Play((0, 8)).to_string(9), "J9");
+        assert_eq!(Vertex::Pass.to_string(9), "pass");
+    }
+
+    #[test]
+    fn display_response() {
+        assert_eq!(Response::Success(Some(1), "".to_string()).to_string(), "=1\n\n");
+        assert_eq!(Response::Failure(None, "illegal move".to_string()).to_string(), "? illegal move\n\n");
+    }
+}
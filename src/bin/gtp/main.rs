@@ -0,0 +1,140 @@
+//! A Go engine that speaks the Go Text Protocol over stdin and stdout.
+//!
+//! ```text
+//! mcts-gtp [--threads N] [--move-time SECONDS] [--verbose]
+//! ```
+
+mod engine;
+mod goban;
+mod gtp;
+
+use self::{engine::Engine, gtp::{parse_player, Command, Response, Vertex}};
+use std::{env, io::{self, BufRead, Write}, time::Duration};
+
+const KNOWN_COMMANDS: &[&str] = &[
+    "boardsize",
+    "clear_board",
+    "genmove",
+    "known_command",
+    "komi",
+    "list_commands",
+    "name",
+    "play",
+    "protocol_version",
+    "quit",
+    "showboard",
+    "time_left",
+    "time_settings",
+    "version"
+];
+
+fn parse_seconds(text: &str) -> Result<Duration, String> {
+    text.parse::<f32>()
+        .ok()
+        .filter(|&seconds| seconds >= 0.0)
+        .map(Duration::from_secs_f32)
+        .ok_or_else(|| "syntax error".to_string())
+}
+
+fn parse_arg<T: std::str::FromStr>(args: &[&str], i: usize) -> Result<T, String> {
+    args.get(i)
+        .and_then(|arg| arg.parse::<T>().ok())
+        .ok_or_else(|| "syntax error".to_string())
+}
+
+fn execute(engine: &mut Engine, command: &Command<'_>) -> Result<String, String> {
+    let args = command.args();
+
+    match command.name() {
+        "protocol_version" => Ok("2".to_string()),
+        "name" => Ok("mcts-rs".to_string()),
+        "version" => Ok(env!("CARGO_PKG_VERSION").to_string()),
+        "known_command" => Ok(KNOWN_COMMANDS.contains(&args.first().copied().unwrap_or("")).to_string()),
+        "list_commands" => Ok(KNOWN_COMMANDS.join("\n")),
+        "quit" => Ok(String::new()),
+        "boardsize" => {
+            let size = parse_arg::<usize>(args, 0)?;
+
+            if (2..=25).contains(&size) {
+                engine.set_size(size).map(|_| String::new()).map_err(|_| "unacceptable size".to_string())
+            } else {
+                Err("unacceptable size".to_string())
+            }
+        },
+        "clear_board" => {
+            engine.clear_board();
+            Ok(String::new())
+        },
+        "komi" => {
+            engine.set_komi(parse_arg::<f32>(args, 0)?);
+            Ok(String::new())
+        },
+        "play" => {
+            let player = args.first().and_then(|arg| parse_player(arg)).ok_or_else(|| "syntax error".to_string())?;
+            let vertex = args.get(1).and_then(|arg| Vertex::parse(arg, engine.size())).ok_or_else(|| "syntax error".to_string())?;
+
+            engine.play(player, vertex).map(|_| String::new())
+        },
+        "genmove" => {
+            let player = args.first().and_then(|arg| parse_player(arg)).ok_or_else(|| "syntax error".to_string())?;
+
+            Ok(engine.genmove(player).to_string(engine.size()))
+        },
+        "time_settings" => {
+            let main_time = parse_seconds(args.first().unwrap_or(&""))?;
+            let byoyomi_time = parse_seconds(args.get(1).unwrap_or(&""))?;
+            let byoyomi_stones = parse_arg::<u32>(args, 2)?;
+
+            engine.set_time_settings(main_time, byoyomi_time, byoyomi_stones);
+            Ok(String::new())
+        },
+        "time_left" => {
+            let player = args.first().and_then(|arg| parse_player(arg)).ok_or_else(|| "syntax error".to_string())?;
+            let time_left = parse_seconds(args.get(1).unwrap_or(&""))?;
+            let stones = parse_arg::<u32>(args, 2)?;
+
+            engine.set_time_left(player, time_left, stones);
+            Ok(String::new())
+        },
+        "showboard" => Ok(engine.showboard()),
+        _ => Err("unknown command".to_string())
+    }
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let mut args = args.iter().map(String::as_str);
+    let mut num_threads = 1;
+    let mut move_time = Duration::from_secs(1);
+    let mut is_verbose = false;
+
+    while let Some(arg) = args.next() {
+        match arg {
+            "--threads" => { num_threads = args.next().and_then(|value| value.parse().ok()).expect("--threads must be a number") },
+            "--move-time" => { move_time = args.next().and_then(|value| parse_seconds(value).ok()).expect("--move-time must be a number of seconds") },
+            "--verbose" => { is_verbose = true },
+            arg => { panic!("unrecognized argument `{}`", arg) }
+        }
+    }
+
+    let mut engine = Engine::new(num_threads, move_time).with_verbose(is_verbose);
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    for line in stdin.lock().lines() {
+        let line = line.expect("could not read from stdin");
+
+        if let Some(command) = Command::parse(&line) {
+            let response = match execute(&mut engine, &command) {
+                Ok(message) => Response::Success(command.id(), message),
+                Err(message) => Response::Failure(command.id(), message)
+            };
+
+            write!(stdout, "{}", response).and_then(|_| stdout.flush()).expect("could not write to stdout");
+
+            if command.name() == "quit" {
+                break
+            }
+        }
+    }
+}
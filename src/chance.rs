@@ -0,0 +1,139 @@
+//! Helpers for chance nodes, whose outcomes are given by `Process::outcomes`
+//! rather than chosen by `Process::select`.
+
+use rand::Rng;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Returns one of the given `outcomes`, sampled according to their
+/// probabilities. The probabilities do not need to sum to one, and an
+/// outcome with a probability of zero is never returned.
+///
+/// # Arguments
+///
+/// * `outcomes` - the outcomes and their probabilities
+/// * `rng` - the source of randomness
+///
+pub fn sample<C>(outcomes: Vec<(C, f32)>, rng: &mut impl Rng) -> Option<C> {
+    let total = outcomes.iter().map(|(_, probability)| probability).sum::<f32>();
+    let mut remaining = rng.gen::<f32>() * total;
+    let mut last = None;
+
+    for (outcome, probability) in outcomes {
+        if remaining < probability {
+            return Some(outcome);
+        }
+
+        remaining -= probability;
+
+        // rounding may leave `remaining` short of the total, in which case
+        // the last outcome that could have been sampled is returned
+        if probability > 0.0 {
+            last = Some(outcome);
+        }
+    }
+
+    last
+}
+
+/// Returns the expected value of the given `outcomes`, normalized by the total
+/// probability of the outcomes so that unexplored outcomes are ignored.
+/// Returns `None` if there are no outcomes.
+///
+/// # Arguments
+///
+/// * `outcomes` - the outcomes and their probabilities
+/// * `value` - returns the value of an outcome
+///
+pub fn expectation<C>(outcomes: impl Iterator<Item=(C, f32)>, value: impl Fn(C) -> f32) -> Option<f32> {
+    let (total_value, total_probability) = outcomes.fold((0.0, 0.0), |(total_value, total_probability), (outcome, probability)| {
+        (total_value + probability * value(outcome), total_probability + probability)
+    });
+
+    if total_probability > 0.0 {
+        Some(total_value / total_probability)
+    } else {
+        None
+    }
+}
+
+/// The expected value of a chance node, which can be stored in its state by
+/// `Process::update_chance` and read back by `Process::update_with_child`.
+pub struct Expectation {
+    value: AtomicU32
+}
+
+impl Default for Expectation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Expectation {
+    pub fn new() -> Self {
+        Self { value: AtomicU32::new(f32::to_bits(f32::NAN)) }
+    }
+
+    /// Returns the expected value, or `None` if it has never been set.
+    pub fn get(&self) -> Option<f32> {
+        let value = f32::from_bits(self.value.load(Ordering::Acquire));
+
+        if value.is_nan() {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    pub fn set(&self, value: f32) {
+        self.value.store(f32::to_bits(value), Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::{mock::StepRng, StdRng}, SeedableRng};
+    use super::*;
+
+    #[test]
+    fn sample_follows_probabilities() {
+        let mut prng = StdRng::seed_from_u64(0xcafed00d);
+        let mut counts = [0; 3];
+
+        for _ in 0..10000 {
+            counts[sample(vec! [(0, 0.25), (1, 0.75), (2, 0.0)], &mut prng).unwrap()] += 1;
+        }
+
+        assert!((2250..2750).contains(&counts[0]), "{:?}", counts);
+        assert!((7250..7750).contains(&counts[1]), "{:?}", counts);
+        assert_eq!(counts[2], 0);
+    }
+
+    #[test]
+    fn sample_never_falls_back_to_impossible_outcome() {
+        let mut rng = StepRng::new(u64::MAX, 0);
+
+        assert_eq!(sample(vec! [(0, 0.03), (1, 0.03), (2, 0.54), (3, 0.0)], &mut rng), Some(2));
+    }
+
+    #[test]
+    fn sample_empty_is_none() {
+        let mut prng = StdRng::seed_from_u64(0xcafed00d);
+
+        assert_eq!(sample::<u32>(vec! [], &mut prng), None);
+    }
+
+    #[test]
+    fn expectation_is_normalized() {
+        assert_eq!(expectation([(1.0, 0.25), (3.0, 0.25)].into_iter(), |value| value), Some(2.0));
+        assert_eq!(expectation([].into_iter(), |value: f32| value), None);
+    }
+
+    #[test]
+    fn expectation_starts_unset() {
+        let expectation = Expectation::new();
+        assert_eq!(expectation.get(), None);
+
+        expectation.set(0.5);
+        assert_eq!(expectation.get(), Some(0.5));
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{edge::Edge, mcts::Mcts, metrics::Counters, node::Node, pending::PendingPolicy, probe_status::ProbeStatus, process::{PerChild, Process, SelectResult}, safe_nonnull::SafeNonNull, trace::Trace};
+use crossbeam_epoch::{self as epoch, Guard};
+use std::thread;
+
+/// The number of times that a node is selected from again because the
+/// selected edge does not exist, before the probe ends as busy.
+const MAX_MISSING_EDGES: u32 = 64;
+
+/// A probe through a search tree that is advanced one step at a time, which
+/// allows several search trees to be descended in lock-step.
+pub(super) struct Cursor<'a, P: Process> {
+    search_tree: &'a Mcts<P>,
+    pin: Guard,
+    trace: Trace<'a, P, Node<P>>,
+    curr: SafeNonNull<Node<P>>,
+    missing_edges: u32,
+    status: Option<ProbeStatus>
+}
+
+impl<'a, P: Process> Cursor<'a, P> {
+    pub(super) fn new(search_tree: &'a Mcts<P>, root: SafeNonNull<Node<P>>) -> Self {
+        let pin = epoch::pin();
+        let trace = match search_tree.pending_policy() {
+            PendingPolicy::Join => Trace::new().with_releaser(search_tree.process(), search_tree.joined()),
+            _ => Trace::new()
+        };
+
+        Self { search_tree, pin, trace, curr: root, missing_edges: 0, status: None }
+    }
+
+    /// Returns the status of the probe if it has ended.
+    pub(super) fn status(&self) -> Option<ProbeStatus> {
+        self.status
+    }
+
+    /// Returns the number of steps taken by this cursor.
+    pub(super) fn depth(&self) -> usize {
+        self.trace.steps().len()
+    }
+
+    /// Advance this cursor by one step, using the edge returned by `select`
+    /// for the current node. Returns the status of the probe if it ended
+    /// with this step. If the selected edge was retired or removed by a
+    /// concurrent `Mcts::cancel`, then the cursor stays at the current node
+    /// and selects again on the next step.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if `select` keeps returning a key that is not an
+    /// edge of the current node.
+    ///
+    /// # Arguments
+    ///
+    /// * `select` - returns the edge to explore from the current node
+    ///
+    pub(super) fn step(&mut self, select: impl FnOnce(&Guard, &Node<P>) -> SelectResult<P::PerChild>) -> Option<ProbeStatus> {
+        if self.status.is_some() {
+            return self.status;
+        }
+
+        if self.search_tree.is_stopped() {
+            self.status = Some(ProbeStatus::Cancelled);
+
+            return self.status;
+        }
+
+        let process = self.search_tree.process();
+        let pin = &self.pin;
+        let curr = self.curr;
+
+        self.status = match select(pin, &curr) {
+            SelectResult::Add(per_child) => {
+                let next_key = per_child.key();
+                let (is_added, retries) = curr.try_expand(pin, per_child);
+                Counters::add(&self.search_tree.counters().expand_retries, retries);
+
+                match curr.edge_ptr(pin, next_key) {
+                    Some(edge) if is_added => self.trace.push_pending(process, curr, edge, true).then_some(ProbeStatus::Expanded),
+                    Some(edge) => self.step_existing(curr, edge),
+                    None => self.step_missing()
+                }
+            },
+            SelectResult::Existing(next_key) => {
+                match curr.edge_ptr(pin, next_key) {
+                    Some(edge) => self.step_existing(curr, edge),
+                    None => self.step_missing()
+                }
+            },
+            SelectResult::None => {
+                Counters::add(&self.search_tree.counters().empty, 1);
+                Some(ProbeStatus::Empty)
+            }
+        };
+
+        self.status
+    }
+
+    /// Follow the existing `edge` of `curr`. If the edge has no child yet
+    /// then the probe either ends at it, or resolves it according to the
+    /// `PendingPolicy` of the search tree if it is already being evaluated.
+    ///
+    /// # Arguments
+    ///
+    /// * `curr` - the node the edge starts from
+    /// * `edge` - the edge to follow
+    ///
+    fn step_existing(&mut self, curr: SafeNonNull<Node<P>>, edge: SafeNonNull<Edge<P, Node<P>>>) -> Option<ProbeStatus> {
+        let process = self.search_tree.process();
+        let counters = self.search_tree.counters();
+        let mut is_waiting = false;
+
+        loop {
+            if let Some(next_curr) = edge.ptr() {
+                self.trace.push(process, curr, edge.key());
+                self.curr = next_curr;
+                self.missing_edges = 0;
+
+                return None;
+            } else if !edge.is_pending() {
+                break;
+            }
+
+            match self.search_tree.pending_policy() {
+                PendingPolicy::Wait if !self.search_tree.is_stopped() => {
+                    if !is_waiting {
+                        is_waiting = true;
+                        Counters::add(&counters.waits, 1);
+                    }
+
+                    thread::yield_now();
+                },
+                PendingPolicy::Join if self.search_tree.try_join(&self.pin, &self.trace, curr, edge) => {
+                    self.trace = Trace::new();
+                    Counters::add(&counters.joins, 1);
+
+                    return Some(ProbeStatus::Joined);
+                },
+                PendingPolicy::Join => {
+                    // pass
+                },
+                _ => break
+            }
+        }
+
+        // the edge was retired by a concurrent cancel, so select again
+        if !self.trace.push_pending(process, curr, edge, false) {
+            return None;
+        }
+
+        Counters::add(&counters.busy, 1);
+
+        Some(ProbeStatus::Busy)
+    }
+
+    /// Stay at the current node since the selected edge does not exist, which
+    /// happens if it was removed by a concurrent `Mcts::cancel`. A `select`
+    /// that returns a key that is not an edge would never end the probe, so
+    /// it ends as busy after `MAX_MISSING_EDGES` attempts.
+    fn step_missing(&mut self) -> Option<ProbeStatus> {
+        debug_assert!(self.missing_edges < MAX_MISSING_EDGES, "the selected edge does not exist");
+
+        if self.missing_edges < MAX_MISSING_EDGES {
+            self.missing_edges += 1;
+            None
+        } else {
+            Counters::add(&self.search_tree.counters().busy, 1);
+            Some(ProbeStatus::Busy)
+        }
+    }
+
+    /// Returns the trace of this cursor, whether or not the probe has ended.
+    pub(super) fn into_trace(self) -> Trace<'a, P, Node<P>> {
+        self.trace
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FakeProcess, FakeState};
+    use super::*;
+
+    fn step_missing(search_tree: &Mcts<FakeProcess>) -> Option<ProbeStatus> {
+        let mut cursor = search_tree.cursor();
+
+        (0..=MAX_MISSING_EDGES).find_map(|_| cursor.step(|_, _| SelectResult::Existing(7)))
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the selected edge does not exist")]
+    fn missing_edge_panics() {
+        step_missing(&Mcts::new(FakeProcess::new(0, 0), FakeState::new()));
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn missing_edge_ends_as_busy() {
+        assert_eq!(step_missing(&Mcts::new(FakeProcess::new(0, 0), FakeState::new())), Some(ProbeStatus::Busy));
+    }
+}
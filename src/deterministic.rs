@@ -0,0 +1,132 @@
+use crate::{mcts::Mcts, node::Node, probe_status::ProbeStatus, process::Process, search_rng::SeededSearchRng, trace::Trace};
+use rand::{rngs::StdRng, SeedableRng};
+use std::thread;
+
+/// A search driver where the same seed and number of threads always yield
+/// the same search tree, regardless of how the threads are scheduled. Probes
+/// are made in batches of one probe per thread on the calling thread, with
+/// `with_search_rng` seeded for the batch. The evaluations of a batch are
+/// then computed in parallel, each with its own generator seeded from its
+/// position in the search, and the search tree is updated in the same order
+/// as it was probed. Since the batches depend on the number of threads, a
+/// different number of threads yields a different search tree.
+pub struct Deterministic {
+    seed: u64,
+    num_threads: usize
+}
+
+fn mix(seed: u64, index: u64) -> u64 {
+    let mut z = seed ^ index.wrapping_add(1).wrapping_mul(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+impl Deterministic {
+    /// Returns a single threaded driver with the given `seed`.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - the seed of every generator used during search
+    ///
+    pub fn new(seed: u64) -> Self {
+        Self { seed, num_threads: 1 }
+    }
+
+    /// Returns this driver where every batch contains `num_threads` probes,
+    /// which are evaluated on that many threads.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_threads` - the number of threads
+    ///
+    pub fn with_threads(self, num_threads: usize) -> Self {
+        Self { num_threads: num_threads.max(1), ..self }
+    }
+
+    /// Returns the seed of this driver.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Search `search_tree` for `num_batches` batches. Every trace from
+    /// `probe` is given to `prepare`, which returns the input of `evaluate` or
+    /// `None` to skip it. The result of `evaluate` is the state to add at the
+    /// end of the trace, if any, and the update to apply. Returns the status
+    /// of every probe in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_tree` - the search tree to search
+    /// * `num_batches` - the number of batches to search
+    /// * `prepare` - returns the input of `evaluate` for a trace
+    /// * `evaluate` - evaluates a single probe, with its own generator
+    ///
+    pub fn search<P, I>(
+        &self,
+        search_tree: &Mcts<P>,
+        num_batches: usize,
+        mut prepare: impl FnMut(&Trace<'_, P, Node<P>>) -> Option<I>,
+        evaluate: impl Fn(I, &mut StdRng) -> (Option<P::State>, P::Update) + Sync
+    ) -> Vec<ProbeStatus>
+        where P: Process, P::State: Send, P::Update: Send, I: Send
+    {
+        let mut statuses = Vec::with_capacity(num_batches * self.num_threads);
+
+        for batch in 0..num_batches {
+            let seeded = SeededSearchRng::new(StdRng::seed_from_u64(mix(self.seed, batch as u64)));
+            let mut traces = Vec::with_capacity(self.num_threads);
+            let mut inputs = Vec::with_capacity(self.num_threads);
+
+            for i in 0..self.num_threads {
+                let (trace, status) = search_tree.probe();
+                let index = (batch * self.num_threads + i) as u64;
+
+                statuses.push(status);
+
+                if status == ProbeStatus::Cancelled {
+                    search_tree.cancel(trace, false);
+                } else if let Some(input) = prepare(&trace) {
+                    traces.push(trace);
+                    inputs.push((index, input));
+                }
+            }
+
+            drop(seeded);
+
+            let evaluate = &evaluate;
+            let seed = self.seed;
+            let results = thread::scope(|scope| {
+                let workers = inputs.into_iter()
+                    .map(|(index, input)| {
+                        scope.spawn(move || {
+                            let mut rng = StdRng::seed_from_u64(mix(!seed, index));
+
+                            evaluate(input, &mut rng)
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                workers.into_iter().map(|worker| worker.join().expect("evaluation panicked")).collect::<Vec<_>>()
+            });
+
+            for (trace, (state, update)) in traces.into_iter().zip(results) {
+                search_tree.update(trace, state, update);
+            }
+        }
+
+        statuses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mix_is_spread() {
+        assert_ne!(mix(0, 0), mix(0, 1));
+        assert_ne!(mix(0, 0), mix(1, 0));
+        assert_eq!(mix(3, 4), mix(3, 4));
+    }
+}
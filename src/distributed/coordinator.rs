@@ -0,0 +1,98 @@
+use crate::{mcts::Mcts, process::PerChild};
+use crossbeam_epoch as epoch;
+use std::{io::{self, Read}, net::TcpListener, thread};
+use super::{read_deltas, Delta, Distribute, WireKey};
+
+/// Merge the given `deltas` from a worker into `search_tree`, adding any
+/// edges and nodes along their paths that are missing. A delta whose path
+/// contains an edge that is not valid, see `Distribute::is_valid_key`, is
+/// dropped, and the number of such deltas is returned.
+///
+/// # Arguments
+///
+/// * `search_tree` - the search tree of the coordinator
+/// * `deltas` - the change in the statistics of the worker
+///
+pub fn merge_deltas<P: Distribute>(search_tree: &Mcts<P>, deltas: &[Delta<<P::PerChild as PerChild>::Key>]) -> usize {
+    let pin = epoch::pin();
+    let process = search_tree.process();
+    let mut num_dropped = 0;
+
+    'deltas: for delta in deltas {
+        let mut child = None;
+
+        for (i, &key) in delta.path().iter().enumerate() {
+            let curr = child.as_deref().unwrap_or(search_tree.root_node());
+
+            if curr.edge(&pin, key).is_none() {
+                if !process.is_valid_key(curr.state(), key) {
+                    num_dropped += 1;
+                    continue 'deltas;
+                }
+
+                curr.try_expand(&pin, process.new_edge(curr.state(), key));
+            }
+
+            let edge = curr.edge(&pin, key).unwrap();
+
+            if i + 1 == delta.path().len() {
+                process.merge_delta(curr.state(), edge.per_child(), delta.value(), delta.visits());
+            } else {
+                if edge.ptr().is_none() {
+                    search_tree.insert_at(&pin, curr, key, process.new_child(curr.state(), key), i as u32 + 1);
+                }
+
+                child = edge.ptr();
+            }
+        }
+    }
+
+    num_dropped
+}
+
+/// Merge every message from a single worker in `reader` into `search_tree`,
+/// until the worker closes the connection. Returns the number of messages.
+///
+/// # Arguments
+///
+/// * `search_tree` - the search tree of the coordinator
+/// * `reader` - the connection to the worker
+///
+pub fn serve<P>(search_tree: &Mcts<P>, mut reader: impl Read) -> io::Result<usize>
+    where P: Distribute, <P::PerChild as PerChild>::Key: WireKey
+{
+    let mut num_messages = 0;
+
+    while let Some(deltas) = read_deltas(&mut reader)? {
+        merge_deltas(search_tree, &deltas);
+        num_messages += 1;
+    }
+
+    Ok(num_messages)
+}
+
+/// Accept `num_workers` connections on `listener`, and merge every message
+/// from each of them into `search_tree` on a separate thread until all of
+/// them have closed their connection.
+///
+/// # Arguments
+///
+/// * `search_tree` - the search tree of the coordinator
+/// * `listener` - where workers connect
+/// * `num_workers` - the number of workers to wait for
+///
+pub fn serve_tcp<P>(search_tree: &Mcts<P>, listener: &TcpListener, num_workers: usize) -> io::Result<()>
+    where P: Distribute, <P::PerChild as PerChild>::Key: WireKey, Mcts<P>: Sync
+{
+    thread::scope(|scope| {
+        let workers = (0..num_workers)
+            .map(|_| listener.accept().map(|(stream, _)| scope.spawn(move || serve(search_tree, stream))))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        for worker in workers {
+            worker.join().expect("worker connection panicked")?;
+        }
+
+        Ok(())
+    })
+}
@@ -0,0 +1,57 @@
+use crate::{process::{PerChild, Process}, uct};
+
+/// A process whose search can be spread across several processes.
+pub trait Distribute: Process {
+    /// Returns the statistics of the given edge that are sent from the
+    /// workers to the coordinator.
+    ///
+    /// # Arguments
+    ///
+    /// * `per_child` - the edge
+    ///
+    fn stats<'a>(&self, per_child: &'a Self::PerChild) -> &'a uct::PerChild;
+
+    /// Returns a new edge with the given `key` from `state`, for when the
+    /// coordinator receives statistics for an edge it does not have.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - the state of the parent
+    /// * `key` - the key of the edge
+    ///
+    fn new_edge(&self, state: &Self::State, key: <Self::PerChild as PerChild>::Key) -> Self::PerChild;
+
+    /// Returns if `state` has an edge with the given `key`, for when the
+    /// coordinator receives statistics for an edge it does not have. Since
+    /// the key comes from another process, it must not be trusted.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - the state of the parent
+    /// * `key` - the key of the edge
+    ///
+    fn is_valid_key(&self, state: &Self::State, key: <Self::PerChild as PerChild>::Key) -> bool;
+
+    /// Returns the state reached by following the edge with the given `key`
+    /// from `state`, for when the coordinator receives statistics below a
+    /// node it does not have.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - the state of the parent
+    /// * `key` - the key of the edge
+    ///
+    fn new_child(&self, state: &Self::State, key: <Self::PerChild as PerChild>::Key) -> Self::State;
+
+    /// Add the total `value` of `visits` evaluations made by a worker to the
+    /// edge `per_child` of `state`.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - the state of the parent
+    /// * `per_child` - the edge
+    /// * `value` - the sum of the values of all evaluations
+    /// * `visits` - the number of evaluations
+    ///
+    fn merge_delta(&self, state: &Self::State, per_child: &Self::PerChild, value: f32, visits: u32);
+}
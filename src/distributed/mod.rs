@@ -0,0 +1,15 @@
+//! Spread a single search across several processes. Every worker searches
+//! its own `Mcts`, and periodically sends the change in the statistics of the
+//! upper part of its tree to a coordinator, which merges them into its own
+//! `Mcts`. The messages can be sent over any stream, such as a TCP or a Unix
+//! socket.
+
+mod coordinator;
+mod definition;
+mod wire;
+mod worker;
+
+pub use self::coordinator::*;
+pub use self::definition::*;
+pub use self::wire::*;
+pub use self::worker::*;
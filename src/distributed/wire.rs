@@ -0,0 +1,260 @@
+use std::io::{self, ErrorKind, Read, Write};
+
+/// The largest body of a message, in bytes, that is written or read.
+pub const MAX_MESSAGE_LEN: usize = 64 << 20;
+
+/// The fewest bytes a delta takes in a message, which is a delta with an
+/// empty path.
+const MIN_DELTA_LEN: usize = 2 + 4 + 4;
+
+/// A key that can be sent between processes.
+pub trait WireKey: Sized {
+    /// Append the encoding of this key to `out`.
+    fn write_to(&self, out: &mut Vec<u8>);
+
+    /// Returns the key encoded at the start of `input`, and advance `input`
+    /// past it.
+    fn read_from(input: &mut &[u8]) -> io::Result<Self>;
+}
+
+fn take<'a>(input: &mut &'a [u8], n: usize) -> io::Result<&'a [u8]> {
+    if input.len() < n {
+        return Err(io::Error::new(ErrorKind::UnexpectedEof, "truncated message"));
+    }
+
+    let (head, tail) = input.split_at(n);
+    *input = tail;
+    Ok(head)
+}
+
+macro_rules! impl_wire_key {
+    ($($t:ty),*) => {
+        $(
+            impl WireKey for $t {
+                fn write_to(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn read_from(input: &mut &[u8]) -> io::Result<Self> {
+                    let bytes = take(input, std::mem::size_of::<$t>())?;
+
+                    Ok(<$t>::from_le_bytes(bytes.try_into().unwrap()))
+                }
+            }
+        )*
+    };
+}
+
+impl_wire_key!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl WireKey for usize {
+    fn write_to(&self, out: &mut Vec<u8>) {
+        (*self as u64).write_to(out);
+    }
+
+    fn read_from(input: &mut &[u8]) -> io::Result<Self> {
+        u64::read_from(input).map(|x| x as usize)
+    }
+}
+
+/// The change in the statistics of a single edge, which is identified by the
+/// keys of every edge from the root up to, and including, itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta<K> {
+    path: Vec<K>,
+    value: f32,
+    visits: u32
+}
+
+impl<K> Delta<K> {
+    /// Returns the change of the edge at the end of `path`, which gained the
+    /// total reward `value` over `visits` evaluations.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - the keys of every edge from the root to the changed edge
+    /// * `value` - the total reward that was added to the edge
+    /// * `visits` - the number of visits that were added to the edge
+    ///
+    pub fn new(path: Vec<K>, value: f32, visits: u32) -> Self {
+        Self { path, value, visits }
+    }
+
+    /// Returns the keys of every edge from the root to the changed edge.
+    pub fn path(&self) -> &[K] {
+        &self.path
+    }
+
+    /// Returns the total reward that was added to the edge.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Returns the number of visits that were added to the edge.
+    pub fn visits(&self) -> u32 {
+        self.visits
+    }
+}
+
+/// Write the given `deltas` as a single message to `writer`. A message is the
+/// length of its body as a little-endian `u32`, followed by the number of
+/// deltas as a `u32`, and then for every delta the length of its path as a
+/// `u16`, every key in the path, its value as an `f32`, and its visits as a
+/// `u32`. Returns an error if a path is longer than `u16::MAX`, or if the
+/// body is longer than `MAX_MESSAGE_LEN`.
+///
+/// # Arguments
+///
+/// * `writer` - where to write the message
+/// * `deltas` - the deltas to write
+///
+pub fn write_deltas<K: WireKey>(writer: &mut impl Write, deltas: &[Delta<K>]) -> io::Result<()> {
+    let mut body = vec! [];
+    (deltas.len() as u32).write_to(&mut body);
+
+    for delta in deltas {
+        let path_len = u16::try_from(delta.path.len())
+            .map_err(|_| io::Error::new(ErrorKind::InvalidInput, "path too long"))?;
+        path_len.write_to(&mut body);
+
+        for key in &delta.path {
+            key.write_to(&mut body);
+        }
+
+        body.extend_from_slice(&delta.value.to_le_bytes());
+        delta.visits.write_to(&mut body);
+    }
+
+    if body.len() > MAX_MESSAGE_LEN {
+        return Err(io::Error::new(ErrorKind::InvalidInput, "message too long"));
+    }
+
+    writer.write_all(&(body.len() as u32).to_le_bytes())?;
+    writer.write_all(&body)?;
+    writer.flush()
+}
+
+/// Returns the deltas of the next message in `reader`, or `None` if the
+/// stream ended before the start of a message. Returns an error if the
+/// stream ends in the middle of a message, or if the message is malformed or
+/// longer than `MAX_MESSAGE_LEN`.
+///
+/// # Arguments
+///
+/// * `reader` - where to read the message from
+///
+pub fn read_deltas<K: WireKey>(reader: &mut impl Read) -> io::Result<Option<Vec<Delta<K>>>> {
+    let mut length = [0; 4];
+    let mut num_read = 0;
+
+    while num_read < length.len() {
+        match reader.read(&mut length[num_read..]) {
+            Ok(0) if num_read == 0 => return Ok(None),
+            Ok(0) => return Err(io::Error::new(ErrorKind::UnexpectedEof, "truncated message length")),
+            Ok(n) => { num_read += n },
+            Err(err) if err.kind() == ErrorKind::Interrupted => {},
+            Err(err) => return Err(err)
+        }
+    }
+
+    let length = u32::from_le_bytes(length) as usize;
+
+    if length > MAX_MESSAGE_LEN {
+        return Err(io::Error::new(ErrorKind::InvalidData, "message too long"));
+    }
+
+    let mut body = vec! [0; length];
+    reader.read_exact(&mut body)?;
+
+    let mut input = body.as_slice();
+    let num_deltas = u32::read_from(&mut input)? as usize;
+
+    if num_deltas > input.len() / MIN_DELTA_LEN {
+        return Err(io::Error::new(ErrorKind::InvalidData, "too many deltas for the message length"));
+    }
+
+    let mut deltas = Vec::with_capacity(num_deltas);
+
+    for _ in 0..num_deltas {
+        let path_len = u16::read_from(&mut input)?;
+        let path = (0..path_len).map(|_| K::read_from(&mut input)).collect::<io::Result<Vec<_>>>()?;
+        let value = f32::from_le_bytes(take(&mut input, 4)?.try_into().unwrap());
+        let visits = u32::read_from(&mut input)?;
+
+        deltas.push(Delta::new(path, value, visits));
+    }
+
+    Ok(Some(deltas))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_round_trip() {
+        let mut out = vec! [];
+        0x1234u16.write_to(&mut out);
+        (-5i32).write_to(&mut out);
+        7usize.write_to(&mut out);
+
+        let mut input = out.as_slice();
+        assert_eq!(u16::read_from(&mut input).unwrap(), 0x1234);
+        assert_eq!(i32::read_from(&mut input).unwrap(), -5);
+        assert_eq!(usize::read_from(&mut input).unwrap(), 7);
+        assert!(input.is_empty());
+        assert!(u8::read_from(&mut input).is_err());
+    }
+
+    #[test]
+    fn deltas_round_trip() {
+        let deltas = vec! [
+            Delta::new(vec! [1u32], 2.5, 4),
+            Delta::new(vec! [1, 7], 0.0, 1)
+        ];
+        let mut stream = vec! [];
+        write_deltas(&mut stream, &deltas).unwrap();
+        write_deltas::<u32>(&mut stream, &[]).unwrap();
+
+        let mut reader = stream.as_slice();
+        assert_eq!(read_deltas::<u32>(&mut reader).unwrap(), Some(deltas));
+        assert_eq!(read_deltas::<u32>(&mut reader).unwrap(), Some(vec! []));
+        assert_eq!(read_deltas::<u32>(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn truncated_message_is_error() {
+        let mut stream = vec! [];
+        write_deltas(&mut stream, &[Delta::new(vec! [1u32], 2.5, 4)]).unwrap();
+        stream.pop();
+
+        assert!(read_deltas::<u32>(&mut stream.as_slice()).is_err());
+    }
+
+    #[test]
+    fn truncated_length_is_error() {
+        let stream = [1u8, 0];
+
+        assert_eq!(read_deltas::<u32>(&mut stream.as_slice()).unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn oversized_message_is_error() {
+        let mut stream = vec! [];
+        (MAX_MESSAGE_LEN as u32 + 1).write_to(&mut stream);
+
+        assert_eq!(read_deltas::<u32>(&mut stream.as_slice()).unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn count_beyond_message_is_error() {
+        let mut body = vec! [];
+        u32::MAX.write_to(&mut body);
+
+        let mut stream = vec! [];
+        (body.len() as u32).write_to(&mut stream);
+        stream.extend_from_slice(&body);
+
+        assert_eq!(read_deltas::<u32>(&mut stream.as_slice()).unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{mcts::Mcts, node::Node, process::PerChild};
+use crossbeam_epoch::Guard;
+use std::{collections::{BTreeMap, HashSet}, io::{self, Write}};
+use super::{write_deltas, Delta, Distribute, WireKey};
+
+/// The statistics that a worker has already sent to the coordinator, so that
+/// only the changes since the last message are sent.
+pub struct Worker<K> {
+    depth: usize,
+    sent: BTreeMap<Vec<K>, (f32, u32)>
+}
+
+impl<K: Copy + Ord + WireKey> Worker<K> {
+    /// Returns a worker that shares the statistics of every edge at most
+    /// `depth` edges from the root.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - the depth of the shared upper part of the tree
+    ///
+    pub fn new(depth: usize) -> Self {
+        Self { depth, sent: BTreeMap::new() }
+    }
+
+    fn collect<P>(&self, pin: &Guard, process: &P, node: &Node<P>, path: &mut Vec<K>, visited: &mut HashSet<*const Node<P>>, deltas: &mut Vec<(Delta<K>, (f32, u32))>)
+        where P: Distribute, P::PerChild: PerChild<Key=K>
+    {
+        if path.len() >= self.depth || !visited.insert(node as *const _) {
+            return;
+        }
+
+        for edge in node.edges(pin) {
+            let stats = process.stats(edge.per_child());
+            let (value, visits) = (stats.total_value(), stats.visits());
+
+            path.push(edge.key());
+
+            let (sent_value, sent_visits) = self.sent.get(path).copied().unwrap_or((0.0, 0));
+
+            if visits > sent_visits {
+                deltas.push((Delta::new(path.clone(), value - sent_value, visits - sent_visits), (value, visits)));
+            }
+
+            if let Some(child) = edge.ptr() {
+                self.collect(pin, process, &child, path, visited, deltas);
+            }
+
+            path.pop();
+        }
+    }
+
+    /// Returns the change in the statistics of the shared upper part of the
+    /// given `search_tree` since the last call.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_tree` - the search tree of this worker
+    ///
+    pub fn deltas<P>(&mut self, search_tree: &Mcts<P>) -> Vec<Delta<K>>
+        where P: Distribute, P::PerChild: PerChild<Key=K>
+    {
+        let (deltas, sent) = self.unsent(search_tree);
+
+        self.commit(&deltas, sent);
+        deltas
+    }
+
+    /// Returns the change in the statistics of the shared upper part of the
+    /// given `search_tree` since the last commit, together with the
+    /// statistics of every changed edge to remember once it has been sent.
+    fn unsent<P>(&self, search_tree: &Mcts<P>) -> (Vec<Delta<K>>, Vec<(f32, u32)>)
+        where P: Distribute, P::PerChild: PerChild<Key=K>
+    {
+        let pin = crossbeam_epoch::pin();
+        let mut deltas = vec! [];
+
+        self.collect(&pin, search_tree.process(), search_tree.root_node(), &mut vec! [], &mut HashSet::new(), &mut deltas);
+        deltas.into_iter().unzip()
+    }
+
+    /// Remember the statistics `sent` of every edge in `deltas` as sent.
+    fn commit(&mut self, deltas: &[Delta<K>], sent: Vec<(f32, u32)>) {
+        for (delta, sent) in deltas.iter().zip(sent) {
+            self.sent.insert(delta.path().to_vec(), sent);
+        }
+    }
+
+    /// Send the change in the statistics of the shared upper part of the
+    /// given `search_tree` since the last call to `writer`. Returns the
+    /// number of edges that were sent. If the write fails then nothing is
+    /// remembered as sent, so the same change is sent again by the next
+    /// call.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_tree` - the search tree of this worker
+    /// * `writer` - the connection to the coordinator
+    ///
+    pub fn sync<P>(&mut self, search_tree: &Mcts<P>, writer: &mut impl Write) -> io::Result<usize>
+        where P: Distribute, P::PerChild: PerChild<Key=K>
+    {
+        let (deltas, sent) = self.unsent(search_tree);
+
+        write_deltas(writer, &deltas)?;
+        self.commit(&deltas, sent);
+
+        Ok(deltas.len())
+    }
+}
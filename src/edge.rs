@@ -1,9 +1,14 @@
 use crate::{process::{Process, PerChild}, safe_nonnull::SafeNonNull};
-use std::{sync::atomic::{AtomicPtr, Ordering}, ptr::null_mut};
+use std::{sync::atomic::{AtomicPtr, AtomicU32, Ordering}, ptr::null_mut};
+
+/// The pending count of an edge that has been retired, and is about to be
+/// removed from its node.
+const RETIRED: u32 = u32::MAX;
 
 pub struct Edge<P: Process, Node> {
     ptr: AtomicPtr<Node>,
-    per_child: P::PerChild
+    per_child: P::PerChild,
+    pending: AtomicU32
 }
 
 impl<P: Process, Node> Edge<P, Node> {
@@ -16,7 +21,8 @@ impl<P: Process, Node> Edge<P, Node> {
     pub(super) fn new(per_child: P::PerChild) -> Self {
         Self {
             ptr: AtomicPtr::new(null_mut()),
-            per_child
+            per_child,
+            pending: AtomicU32::new(0)
         }
     }
 
@@ -50,6 +56,45 @@ impl<P: Process, Node> Edge<P, Node> {
     pub(super) fn try_insert(&self, new_ptr: SafeNonNull<Node>) -> bool {
         self.ptr.compare_exchange(null_mut(), new_ptr.as_ptr(), Ordering::AcqRel, Ordering::Relaxed).is_ok()
     }
+
+    /// Mark this edge as the end of one more trace that is waiting for its
+    /// evaluation. Return true iff this edge has not been retired.
+    pub(super) fn acquire(&self) -> bool {
+        self.pending.fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+            if pending == RETIRED {
+                None
+            } else {
+                Some(pending + 1)
+            }
+        }).is_ok()
+    }
+
+    /// Remove the mark of a trace that has been updated or abandoned.
+    pub(super) fn release(&self) {
+        self.pending.fetch_sub(1, Ordering::AcqRel);
+    }
+
+    /// Returns the number of traces that are waiting for the evaluation of
+    /// this edge, which is zero for a retired edge.
+    pub(super) fn pending(&self) -> u32 {
+        match self.pending.load(Ordering::Acquire) {
+            RETIRED => 0,
+            pending => pending
+        }
+    }
+
+    /// Returns if this edge has no destination yet, but is being evaluated
+    /// by at least one trace.
+    pub(super) fn is_pending(&self) -> bool {
+        self.ptr().is_none() && self.pending() > 0
+    }
+
+    /// Retire this edge if it has no destination and the caller holds the
+    /// only mark, after which no trace can end at it. Return true iff this
+    /// edge was retired.
+    pub(super) fn try_retire(&self) -> bool {
+        self.ptr().is_none() && self.pending.compare_exchange(1, RETIRED, Ordering::AcqRel, Ordering::Relaxed).is_ok()
+    }
 }
 
 #[cfg(test)]
@@ -90,4 +135,26 @@ mod tests {
         assert!(!edge.try_insert(ptr2));
         assert_eq!(edge.ptr(), Some(ptr1));
     }
+
+    #[test]
+    fn retire_requires_a_single_mark() {
+        let edge: Edge<FakeProcess, ()> = Edge::new(FakePerChild::new(1));
+
+        assert!(edge.acquire());
+        assert!(edge.acquire());
+        assert!(!edge.try_retire());
+
+        edge.release();
+        assert!(edge.try_retire());
+        assert!(!edge.acquire());
+    }
+
+    #[test]
+    fn retire_fails_when_ptr_is_not_null() {
+        let edge: Edge<FakeProcess, ()> = Edge::new(FakePerChild::new(1));
+
+        assert!(edge.acquire());
+        assert!(edge.try_insert(SafeNonNull::new(())));
+        assert!(!edge.try_retire());
+    }
 }
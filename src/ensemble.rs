@@ -0,0 +1,121 @@
+use crate::{mcts::Mcts, process::{PerChild, Process}};
+use crossbeam_epoch as epoch;
+use std::{collections::BTreeMap, thread};
+
+/// A process whose edge statistics from several independent search trees can
+/// be combined.
+pub trait Merge: Process {
+    /// Returns the combined statistics of the same edge from several search
+    /// trees, which all have the same key.
+    ///
+    /// # Arguments
+    ///
+    /// * `per_children` - the statistics of the edge in every search tree
+    ///   where it exists, which is never empty
+    ///
+    fn merge(&self, per_children: &[&Self::PerChild]) -> Self::PerChild;
+
+    /// Add the statistics of the root `state` of several other search trees
+    /// to the root `state` of the search tree that is kept, so that they
+    /// agree with its merged edges. Does nothing by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - the root state of the search tree that is kept
+    /// * `others` - the root states of every other search tree
+    ///
+    fn merge_root(&self, _state: &Self::State, _others: &[&Self::State]) {
+        // pass
+    }
+}
+
+/// Several independent search trees of the same root, which are searched on
+/// separate threads (root parallelization) and whose root edges are then
+/// merged. This avoids the contention between threads that search the same
+/// tree, at the cost of duplicated work.
+pub struct Ensemble<P: Process> {
+    search_trees: Vec<Mcts<P>>
+}
+
+impl<P: Merge> Ensemble<P> {
+    /// Returns an ensemble of the given `search_trees`, which should all have
+    /// the same root state.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_trees` - the independent search trees
+    ///
+    /// # Panics
+    ///
+    /// If `search_trees` is empty.
+    ///
+    pub fn new(search_trees: Vec<Mcts<P>>) -> Self {
+        assert!(!search_trees.is_empty());
+
+        Self { search_trees }
+    }
+
+    /// Returns the independent search trees of this ensemble.
+    pub fn search_trees(&self) -> &[Mcts<P>] {
+        &self.search_trees
+    }
+
+    /// Search every tree on its own thread by calling `worker` with the index
+    /// and the search tree, and wait for all of them to return.
+    ///
+    /// # Arguments
+    ///
+    /// * `worker` - searches a single tree until its budget is exhausted
+    ///
+    pub fn search(&self, worker: impl Fn(usize, &Mcts<P>) + Sync) where Mcts<P>: Sync {
+        let worker = &worker;
+
+        thread::scope(|scope| {
+            for (i, search_tree) in self.search_trees.iter().enumerate() {
+                scope.spawn(move || worker(i, search_tree));
+            }
+        });
+    }
+
+    /// Returns the merged statistics of every edge of the root, from every
+    /// search tree, in order of their keys.
+    pub fn merged(&self) -> Vec<P::PerChild> {
+        let pin = epoch::pin();
+        let mut by_key = BTreeMap::new();
+
+        for search_tree in &self.search_trees {
+            for edge in search_tree.root_node().edges(&pin) {
+                by_key.entry(edge.key()).or_insert_with(Vec::new).push(edge.per_child());
+            }
+        }
+
+        let process = self.search_trees[0].process();
+
+        by_key.into_values().map(|per_children| process.merge(&per_children)).collect()
+    }
+
+    /// Returns the key of the _best_ edge of the root according to the merged
+    /// statistics of every search tree.
+    pub fn best(&self) -> Option<<P::PerChild as PerChild>::Key> {
+        let search_tree = &self.search_trees[0];
+        let merged = self.merged();
+
+        search_tree.process().best(search_tree.root(), merged.iter())
+    }
+
+    /// Returns a single search tree, which is the first search tree of this
+    /// ensemble with the statistics of its root, and of its root edges,
+    /// replaced by the merged statistics of every search tree.
+    pub fn into_tree(mut self) -> Mcts<P> {
+        let merged = self.merged();
+        let mut search_tree = self.search_trees.swap_remove(0);
+        let others = self.search_trees.iter().map(|other| other.root()).collect::<Vec<_>>();
+
+        search_tree.process().merge_root(search_tree.root(), &others);
+        search_tree.root_node().add_visits(self.search_trees.iter().map(|other| other.root_node().visits()).sum());
+
+        drop(self);
+        search_tree.replace_root_edges(merged);
+        search_tree
+    }
+}
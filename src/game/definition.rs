@@ -0,0 +1,60 @@
+/// The rules of a turn-based game, from which a complete monte-carlo process
+/// can be derived using `GameProcess`.
+pub trait Game: Clone + 'static {
+    type Move: Copy + Ord;
+
+    /// Returns all moves that are legal to play in this position.
+    fn legal_moves(&self) -> Vec<Self::Move>;
+
+    /// Play the given move, which must be legal.
+    ///
+    /// # Arguments
+    ///
+    /// * `mv` - the move to play
+    ///
+    fn play(&mut self, mv: Self::Move);
+
+    /// Returns the number of players in this game.
+    fn num_players(&self) -> usize {
+        2
+    }
+
+    /// Returns the index of the player whose turn it is, which must be less
+    /// than `num_players`.
+    fn current_player(&self) -> usize;
+
+    /// Returns if the game is over.
+    fn is_terminal(&self) -> bool;
+
+    /// Returns the reward for the given `player` in a terminal position,
+    /// between `0.0` (a loss) and `1.0` (a win).
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the index of the player
+    ///
+    fn reward(&self, player: usize) -> f32;
+
+    /// Returns the hash of this position, if positions should be shared
+    /// between transpositions.
+    fn hash(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the 128-bit hash of this position, if positions should be
+    /// shared between transpositions. The default widens `hash`.
+    fn hash128(&self) -> Option<u128> {
+        self.hash().map(u128::from)
+    }
+
+    /// Returns if this position is the same as `other`, which has the same
+    /// hash. The default trusts the hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the position with the same hash as this position
+    ///
+    fn same_as(&self, _other: &Self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,9 @@
+mod definition;
+mod per_child;
+mod process;
+mod state;
+
+pub use self::definition::*;
+pub use self::per_child::*;
+pub use self::process::*;
+pub use self::state::*;
@@ -0,0 +1,129 @@
+use crate::{maxn, process};
+use crossbeam_epoch::{self as epoch, Atomic, Owned};
+use std::{mem, sync::{atomic::Ordering, Arc}};
+use super::{state::{average, Values}, Backup, Game};
+
+/// The statistics for a single move in a `GameProcess`.
+pub struct GamePerChild<G: Game> {
+    mv: G::Move,
+    maxn: maxn::PerChild,
+    child: Atomic<(usize, Values)>
+}
+
+impl<G: Game> Drop for GamePerChild<G> {
+    fn drop(&mut self) {
+        unsafe {
+            let child = mem::replace(&mut self.child, Atomic::null());
+
+            if !child.load(Ordering::Relaxed, epoch::unprotected()).is_null() {
+                drop(child.into_owned());
+            }
+        }
+    }
+}
+
+impl<G: Game> process::PerChild for GamePerChild<G> {
+    type Key = G::Move;
+
+    fn key(&self) -> Self::Key {
+        self.mv
+    }
+}
+
+impl<G: Game> GamePerChild<G> {
+    pub fn new(mv: G::Move) -> Self {
+        Self { mv, maxn: maxn::PerChild::new(), child: Atomic::null() }
+    }
+
+    /// Returns the statistics for the move `mv` with the given `maxn`
+    /// statistics.
+    ///
+    /// # Arguments
+    ///
+    /// * `mv` - the move of this edge
+    /// * `maxn` - the statistics of this edge
+    ///
+    pub fn with_maxn(mv: G::Move, maxn: maxn::PerChild) -> Self {
+        Self { mv, maxn, child: Atomic::null() }
+    }
+
+    /// Returns the move of this edge.
+    pub fn mv(&self) -> G::Move {
+        self.mv
+    }
+
+    /// Returns the number of times this move has been visited.
+    pub fn visits(&self) -> u32 {
+        self.maxn.visits()
+    }
+
+    /// Returns the average reward of this move, for the player that plays it.
+    pub fn value(&self) -> f32 {
+        self.maxn.value()
+    }
+
+    /// Returns the statistics of this move.
+    pub fn maxn(&self) -> &maxn::PerChild {
+        &self.maxn
+    }
+
+    /// Returns the average reward of the position this move leads to, for
+    /// the player that plays it, over every evaluation that reached that
+    /// position through any parent. This is read from the position itself,
+    /// once this move has been updated with it when the process uses
+    /// `Backup::Dag`.
+    pub fn child_value(&self) -> Option<f32> {
+        let pin = epoch::pin();
+
+        unsafe { self.child.load(Ordering::Acquire, &pin).as_ref() }
+            .and_then(|(player, values)| average(&values[*player]))
+    }
+
+    /// Returns the upper confidence bound of this move, where the average
+    /// reward is taken from the child position if the given `backup` is
+    /// `Backup::Dag` and it is known.
+    ///
+    /// # Arguments
+    ///
+    /// * `total_visits` - the number of visits to the parent
+    /// * `backup` - how evaluations are backed up
+    ///
+    #[inline]
+    pub fn uct(&self, total_visits: u32, backup: Backup) -> f32 {
+        match (backup, self.child_value()) {
+            (Backup::Dag, Some(value)) => self.maxn.uct_with_value(value, total_visits),
+            _ => self.maxn.uct(total_visits)
+        }
+    }
+
+    /// Returns the player that plays this move, and the average rewards of
+    /// the position it leads to, if they are shared with this move.
+    pub(super) fn child(&self) -> Option<(usize, Values)> {
+        let pin = epoch::pin();
+
+        unsafe { self.child.load(Ordering::Acquire, &pin).as_ref() }.cloned()
+    }
+
+    /// Share the average rewards of the position this move leads to, as seen
+    /// by the given `player` that plays this move, unless they already are.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the index of the player that plays this move
+    /// * `values` - the average rewards of the child position
+    ///
+    pub(super) fn set_child(&self, player: usize, values: &Values) {
+        let pin = epoch::pin();
+        let current = self.child.load(Ordering::Acquire, &pin);
+
+        if unsafe { current.as_ref() }.map(|(_, current_values)| Arc::ptr_eq(current_values, values)).unwrap_or(false) {
+            return;
+        }
+
+        let prev = self.child.swap(Owned::new((player, values.clone())), Ordering::AcqRel, &pin);
+
+        if !prev.is_null() {
+            unsafe { pin.defer_destroy(prev) };
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{distributed::Distribute, ensemble::Merge, maxn, mcts::Mcts, probe_status::ProbeStatus, process::{PerChild, Process, SelectResult}, uct};
+use rand::{seq::SliceRandom, Rng};
+use std::marker::PhantomData;
+use super::{Game, GamePerChild, GameState};
+
+/// How the evaluations of a `GameProcess` are backed up through positions
+/// that are shared between transpositions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Backup {
+    /// Every edge only uses the evaluations that passed through itself, as
+    /// if the search tree was a tree.
+    #[default]
+    Tree,
+
+    /// Every edge uses the average reward of the position it leads to, which
+    /// includes the evaluations that reached it through other parents, while
+    /// the visits of the edge are still used for exploration (UCT3).
+    Dag
+}
+
+/// A monte-carlo process derived from the rules of a `Game`, using UCT for
+/// selection, uniformly random games for evaluation, and max^n backups so
+/// that every player maximizes their own reward.
+pub struct GameProcess<G: Game> {
+    expand_threshold: u32,
+    backup: Backup,
+    game: PhantomData<fn() -> G>
+}
+
+impl<G: Game> Default for GameProcess<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: Game> GameProcess<G> {
+    /// Returns a process where a position is added to the search tree after
+    /// its parent has been visited eight times.
+    pub fn new() -> Self {
+        Self { expand_threshold: 8, backup: Backup::Tree, game: PhantomData }
+    }
+
+    /// Returns this process where a position is added to the search tree
+    /// after its parent has been visited `expand_threshold` times.
+    ///
+    /// # Arguments
+    ///
+    /// * `expand_threshold` - the number of visits before expanding
+    ///
+    pub fn with_expand_threshold(self, expand_threshold: u32) -> Self {
+        Self { expand_threshold, ..self }
+    }
+
+    /// Returns this process where evaluations are backed up with the given
+    /// `backup` scheme.
+    ///
+    /// # Arguments
+    ///
+    /// * `backup` - how to back up evaluations through transpositions
+    ///
+    pub fn with_backup(self, backup: Backup) -> Self {
+        Self { backup, ..self }
+    }
+
+    /// Returns the reward of every player at the end of a uniformly random
+    /// game played from `game`.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - the position to start the random game from
+    /// * `rng` - the source of randomness
+    ///
+    pub fn rollout(&self, game: &G, rng: &mut impl Rng) -> maxn::Update {
+        let mut game = game.clone();
+
+        while !game.is_terminal() {
+            match game.legal_moves().choose(rng) {
+                Some(&mv) => game.play(mv),
+                None => break
+            }
+        }
+
+        maxn::Update::new((0..game.num_players()).map(|player| game.reward(player)))
+    }
+
+    fn first_unexplored(&self, state: &GameState<G>, explored: &[G::Move]) -> Option<G::Move> {
+        state.game().legal_moves().into_iter().find(|mv| !explored.contains(mv))
+    }
+
+    /// Returns the edge with the best UCT score among the given `edges` that
+    /// are not skipped, or the first unexplored move if no such edge is
+    /// better than the baseline. Skipped edges still count as explored, and
+    /// the best of them is only returned if there is nothing else.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - the position to select from
+    /// * `edges` - every explored edge, and whether it is skipped
+    ///
+    fn select_from<'a>(&self, state: &GameState<G>, edges: impl Iterator<Item=(&'a GamePerChild<G>, bool)>) -> SelectResult<GamePerChild<G>> where G: 'a {
+        if state.game().is_terminal() {
+            return SelectResult::None;
+        }
+
+        let total_visits = state.visits();
+        let mut explored = vec! [];
+        let mut best_edge: Option<(f32, &GamePerChild<G>)> = None;
+        let mut best_skipped: Option<(f32, &GamePerChild<G>)> = None;
+
+        for (edge, is_skipped) in edges {
+            let uct = edge.uct(total_visits, self.backup);
+            let best = if is_skipped { &mut best_skipped } else { &mut best_edge };
+
+            if best.map(|(best_uct, _)| uct > best_uct).unwrap_or(true) {
+                *best = Some((uct, edge));
+            }
+
+            explored.push(edge.key());
+        }
+
+        match best_edge {
+            Some((uct, best_edge)) if uct > uct::State::baseline(total_visits) => {
+                SelectResult::Existing(best_edge.key())
+            },
+            Some((_, best_edge)) => {
+                self.first_unexplored(state, &explored)
+                    .map(|mv| SelectResult::Add(GamePerChild::new(mv)))
+                    .unwrap_or(SelectResult::Existing(best_edge.key()))
+            },
+            None => {
+                self.first_unexplored(state, &explored)
+                    .map(|mv| SelectResult::Add(GamePerChild::new(mv)))
+                    .or_else(|| best_skipped.map(|(_, best_edge)| SelectResult::Existing(best_edge.key())))
+                    .unwrap_or(SelectResult::None)
+            }
+        }
+    }
+}
+
+impl<G: Game> Process for GameProcess<G> {
+    type State = GameState<G>;
+    type PerChild = GamePerChild<G>;
+    type Update = maxn::Update;
+
+    fn best<'a>(&self, _: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> Option<<Self::PerChild as PerChild>::Key> where Self::PerChild: 'a {
+        edges.max_by_key(|edge| edge.visits()).map(|edge| edge.key())
+    }
+
+    fn select<'a>(&self, state: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        self.select_from(state, edges.map(|edge| (edge, false)))
+    }
+
+    fn select_skipping<'a>(&self, state: &Self::State, edges: impl Iterator<Item=(&'a Self::PerChild, bool)>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        self.select_from(state, edges)
+    }
+
+    fn update(&self, state: &Self::State, per_child: &Self::PerChild, update: &Self::Update, _: bool) {
+        state.update();
+        per_child.maxn().update(state.game().current_player(), update);
+    }
+
+    fn update_with_child(&self, state: &Self::State, per_child: &Self::PerChild, update: &Self::Update, child: Option<&Self::State>) {
+        self.update(state, per_child, update, child.is_some());
+
+        if let (Backup::Dag, Some(child)) = (self.backup, child) {
+            child.record(update);
+            per_child.set_child(state.game().current_player(), child.values());
+        }
+    }
+}
+
+impl<G: Game> Merge for GameProcess<G> {
+    /// The merged edge keeps sharing the child value of the first edge,
+    /// until it is updated with a different child.
+    fn merge(&self, per_children: &[&Self::PerChild]) -> Self::PerChild {
+        let maxn = maxn::PerChild::merged(per_children.iter().map(|per_child| per_child.maxn()));
+        let merged = GamePerChild::with_maxn(per_children[0].mv(), maxn);
+
+        if let Some((player, values)) = per_children[0].child() {
+            merged.set_child(player, &values);
+        }
+
+        merged
+    }
+
+    fn merge_root(&self, state: &Self::State, others: &[&Self::State]) {
+        for other in others {
+            state.add(other);
+        }
+    }
+}
+
+impl<G: Game> Distribute for GameProcess<G> {
+    fn stats<'a>(&self, per_child: &'a Self::PerChild) -> &'a uct::PerChild {
+        per_child.maxn().stats()
+    }
+
+    fn new_edge(&self, _: &Self::State, mv: G::Move) -> Self::PerChild {
+        GamePerChild::new(mv)
+    }
+
+    fn is_valid_key(&self, state: &Self::State, mv: G::Move) -> bool {
+        !state.game().is_terminal() && state.game().legal_moves().contains(&mv)
+    }
+
+    fn new_child(&self, state: &Self::State, mv: G::Move) -> Self::State {
+        let mut game = state.game().clone();
+        game.play(mv);
+
+        GameState::new(game)
+    }
+
+    fn merge_delta(&self, state: &Self::State, per_child: &Self::PerChild, value: f32, visits: u32) {
+        state.add_visits(visits);
+        per_child.maxn().stats().add(value, visits);
+    }
+}
+
+/// Probe the given `search_tree` once, evaluate the resulting position with a
+/// random game, and update the search tree with the result. Returns the
+/// status of the probe, which is `ProbeStatus::Empty` if the root is a
+/// terminal position.
+///
+/// # Arguments
+///
+/// * `search_tree` - the search tree to probe
+/// * `rng` - the source of randomness for the random game
+///
+pub fn search_once<G: Game>(search_tree: &Mcts<GameProcess<G>>, rng: &mut impl Rng) -> ProbeStatus {
+    let (trace, status) = search_tree.probe();
+
+    if status == ProbeStatus::Cancelled {
+        search_tree.cancel(trace, false);
+    } else if let Some(last_step) = trace.steps().last() {
+        let process = search_tree.process();
+        let (game, is_expandable) = last_step.map(|state, per_child| {
+            let mut game = state.game().clone();
+            game.play(per_child.key());
+
+            (game, state.visits() >= process.expand_threshold)
+        });
+        let update = process.rollout(&game, rng);
+
+        if is_expandable && trace.is_pending() {
+            search_tree.update(trace, Some(GameState::new(game)), update);
+        } else {
+            search_tree.update(trace, None, update);
+        }
+    }
+
+    status
+}
@@ -0,0 +1,101 @@
+use crate::{maxn, process, uct};
+use std::sync::Arc;
+use super::Game;
+
+/// The average reward of every player over every evaluation that reached a
+/// position, which is shared with the edges that lead to it.
+pub(super) type Values = Arc<[uct::PerChild]>;
+
+/// A position in a `GameProcess`.
+pub struct GameState<G: Game> {
+    game: G,
+    uct: uct::State,
+    values: Values
+}
+
+impl<G: Game> process::State for GameState<G> {
+    fn hash(&self) -> Option<u64> {
+        self.game.hash()
+    }
+
+    fn hash128(&self) -> Option<u128> {
+        self.game.hash128()
+    }
+
+    fn same_as(&self, other: &Self) -> bool {
+        self.game.same_as(&other.game)
+    }
+}
+
+impl<G: Game> GameState<G> {
+    pub fn new(game: G) -> Self {
+        let values = (0..game.num_players()).map(|_| uct::PerChild::new()).collect();
+
+        Self { game, uct: uct::State::new(), values }
+    }
+
+    /// Returns the game in this position.
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+
+    /// Returns the number of times this position has been visited.
+    pub fn visits(&self) -> u32 {
+        self.uct.visits()
+    }
+
+    /// Returns the average reward of the given `player` over every evaluation
+    /// that reached this position, through any of its parents, or `None` if
+    /// no evaluation has been recorded. This is only recorded when the
+    /// process uses `Backup::Dag`.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the index of the player
+    ///
+    pub fn value(&self, player: usize) -> Option<f32> {
+        average(&self.values[player])
+    }
+
+    /// Returns the average reward of every player in this position, which
+    /// keeps being updated as long as this position is.
+    pub(super) fn values(&self) -> &Values {
+        &self.values
+    }
+
+    pub(super) fn update(&self) {
+        self.uct.update();
+    }
+
+    pub(super) fn record(&self, up: &maxn::Update) {
+        for (player, values) in self.values.iter().enumerate() {
+            values.update(&up.uct(player));
+        }
+    }
+
+    /// Add the visits and the average rewards of `other`, which is the same
+    /// position in another search tree, to this position.
+    pub(super) fn add(&self, other: &Self) {
+        self.uct.add(other.visits());
+
+        for (values, other_values) in self.values.iter().zip(other.values.iter()) {
+            values.add(other_values.total_value(), other_values.visits());
+        }
+    }
+
+    pub(super) fn add_visits(&self, visits: u32) {
+        self.uct.add(visits);
+    }
+}
+
+/// Returns the average reward in the given `values`, or `None` if no
+/// evaluation has been recorded.
+pub(super) fn average(values: &uct::PerChild) -> Option<f32> {
+    let visits = values.visits();
+
+    if visits > 0 {
+        Some(values.win_rate(values.total_value(), visits))
+    } else {
+        None
+    }
+}
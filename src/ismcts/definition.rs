@@ -0,0 +1,37 @@
+use crate::game::Game;
+use rand::Rng;
+
+/// A game with hidden information, where each player only observes part of
+/// every position.
+pub trait Determinize: Game {
+    /// Returns the hash of the information set of `observer` in this
+    /// position, which only depends on what `observer` knows about it.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - the index of the observing player
+    ///
+    fn information_set(&self, observer: usize) -> u64;
+
+    /// Returns a position that `observer` cannot tell apart from this one,
+    /// with all information hidden from `observer` sampled at random.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - the index of the observing player
+    /// * `rng` - the source of randomness
+    ///
+    fn determinize<R: Rng + ?Sized>(&self, observer: usize, rng: &mut R) -> Self;
+
+    /// Returns the move that `observer` sees when `mv` is played in this
+    /// position, which is `mv` unless part of it is hidden from `observer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `mv` - the move being played
+    /// * `observer` - the index of the observing player
+    ///
+    fn observe(&self, mv: Self::Move, _observer: usize) -> Self::Move {
+        mv
+    }
+}
@@ -0,0 +1,11 @@
+mod definition;
+mod multiple_observer;
+mod per_child;
+mod process;
+mod state;
+
+pub use self::definition::*;
+pub use self::multiple_observer::*;
+pub use self::per_child::*;
+pub use self::process::*;
+pub use self::state::*;
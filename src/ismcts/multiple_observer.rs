@@ -0,0 +1,116 @@
+use crate::{game::GameProcess, mcts::Mcts, open_loop::OpenLoopProcess, probe_status::ProbeStatus, process::{PerChild, SelectResult}};
+use rand::Rng;
+use super::{Determinize, IsPerChild, IsmctsProcess};
+
+/// One search tree of information sets per player (MO-ISMCTS), which are
+/// descended in lock-step through the same determinization. Each player
+/// selects their own moves in their own tree, while the other trees follow
+/// the move as it is observed by their owner, so that no player can exploit
+/// a move that is hidden from them.
+pub struct MultipleObserver<G: Determinize> {
+    search_trees: Vec<Mcts<IsmctsProcess<G>>>
+}
+
+impl<G: Determinize> MultipleObserver<G> {
+    /// Returns a search tree for every player in the given `game`.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - the position at the root of the search trees
+    ///
+    pub fn new(game: &G) -> Self {
+        let search_trees = (0..game.num_players())
+            .map(|observer| Mcts::new_open_loop(IsmctsProcess::new(observer), game))
+            .collect();
+
+        Self { search_trees }
+    }
+
+    /// Returns the search tree of the given `observer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - the index of the observing player
+    ///
+    pub fn search_tree(&self, observer: usize) -> &Mcts<IsmctsProcess<G>> {
+        &self.search_trees[observer]
+    }
+
+    /// Probe every search tree once through a determinization of `game` from
+    /// the perspective of the player to move, evaluate the resulting position
+    /// with a random game, and update every search tree with the result.
+    /// Returns the status of the probe that ended the descent.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - the actual position at the root of the search trees
+    /// * `rng` - the source of randomness
+    ///
+    pub fn search_once(&self, game: &G, rng: &mut impl Rng) -> ProbeStatus {
+        let mut game = game.determinize(game.current_player(), rng);
+        let mut cursors = self.search_trees.iter().map(|search_tree| search_tree.cursor()).collect::<Vec<_>>();
+        let status = loop {
+            let player = game.current_player();
+            let process = self.search_trees[player].process();
+            let skip_pending = self.search_trees[player].skips_pending();
+            let depth = cursors[player].depth();
+            let mut mv = None;
+            let status = cursors[player].step(|pin, node| {
+                let select_result = process.select_open_loop(node.state(), &game, node.select_edges(pin, skip_pending));
+
+                match &select_result {
+                    SelectResult::Add(per_child) => { mv = Some(per_child.key()) },
+                    SelectResult::Existing(key) => { mv = Some(*key) },
+                    SelectResult::None => {}
+                }
+
+                select_result
+            });
+
+            // the selected edge was retired by a concurrent cancel, so select
+            // again before any observer follows the move
+            if status.is_none() && cursors[player].depth() == depth {
+                continue;
+            }
+
+            if let Some(mv) = mv {
+                for (observer, cursor) in cursors.iter_mut().enumerate().filter(|&(observer, _)| observer != player) {
+                    let observed = game.observe(mv, observer);
+                    let depth = cursor.depth();
+
+                    // every observer must follow the same move, even if its
+                    // edge is retired by a concurrent cancel
+                    while cursor.status().is_none() && cursor.depth() == depth {
+                        cursor.step(|pin, node| {
+                            if node.edge(pin, observed).is_some() {
+                                SelectResult::Existing(observed)
+                            } else {
+                                SelectResult::Add(IsPerChild::new(observed))
+                            }
+                        });
+                    }
+                }
+
+                game = process.step(&game, mv);
+            }
+
+            if let Some(status) = status.or_else(|| cursors.iter().find_map(|cursor| cursor.status())) {
+                break status;
+            }
+        };
+
+        let update = GameProcess::<G>::new().rollout(&game, rng);
+
+        for (search_tree, cursor) in self.search_trees.iter().zip(cursors) {
+            let trace = cursor.into_trace();
+
+            if status == ProbeStatus::Cancelled {
+                search_tree.cancel(trace, false);
+            } else if !trace.is_empty() {
+                search_tree.update_open_loop(trace, &game, update.clone());
+            }
+        }
+
+        status
+    }
+}
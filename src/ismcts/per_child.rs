@@ -0,0 +1,61 @@
+use crate::{maxn, process};
+use std::sync::atomic::{AtomicU32, Ordering};
+use super::Determinize;
+
+/// The statistics for a single move in an `IsmctsProcess`, including how
+/// many times the move was available when its information set was visited.
+pub struct IsPerChild<G: Determinize> {
+    mv: G::Move,
+    maxn: maxn::PerChild,
+    availability: AtomicU32
+}
+
+impl<G: Determinize> process::PerChild for IsPerChild<G> {
+    type Key = G::Move;
+
+    fn key(&self) -> Self::Key {
+        self.mv
+    }
+}
+
+impl<G: Determinize> IsPerChild<G> {
+    pub fn new(mv: G::Move) -> Self {
+        Self { mv, maxn: maxn::PerChild::new(), availability: AtomicU32::new(0) }
+    }
+
+    /// Returns the move of this edge.
+    pub fn mv(&self) -> G::Move {
+        self.mv
+    }
+
+    /// Returns the number of times this move has been visited.
+    pub fn visits(&self) -> u32 {
+        self.maxn.visits()
+    }
+
+    /// Returns the number of times this move was legal in the determinization
+    /// when its information set was visited.
+    pub fn availability(&self) -> u32 {
+        self.availability.load(Ordering::Relaxed)
+    }
+
+    /// Returns the average reward of this move, for the player that plays it.
+    pub fn value(&self) -> f32 {
+        self.maxn.value()
+    }
+
+    /// Returns the upper confidence bound of this move, where the number of
+    /// visits to the parent is replaced by the availability of the move.
+    pub fn uct(&self) -> f32 {
+        self.maxn.uct(self.availability())
+    }
+
+    /// Returns the statistics of this move.
+    pub fn maxn(&self) -> &maxn::PerChild {
+        &self.maxn
+    }
+
+    pub(super) fn mark_available(&self) {
+        self.availability.fetch_add(1, Ordering::AcqRel);
+    }
+}
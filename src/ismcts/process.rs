@@ -0,0 +1,127 @@
+use crate::{game::GameProcess, maxn, mcts::Mcts, open_loop::OpenLoopProcess, probe_status::ProbeStatus, process::{PerChild, Process, SelectResult}};
+use rand::Rng;
+use std::marker::PhantomData;
+use super::{Determinize, InformationSet, IsPerChild};
+
+/// A monte-carlo process for a game with hidden information, where every node
+/// in the search tree is an information set of a single `observer`
+/// (SO-ISMCTS). Every probe is made through a determinization that is
+/// consistent with the information set of the `observer` at the root, using
+/// `Mcts::probe_open_loop`.
+pub struct IsmctsProcess<G: Determinize> {
+    observer: usize,
+    game: PhantomData<fn() -> G>
+}
+
+impl<G: Determinize> IsmctsProcess<G> {
+    /// Returns a process where every node is an information set of the given
+    /// `observer`.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - the index of the observing player
+    ///
+    pub fn new(observer: usize) -> Self {
+        Self { observer, game: PhantomData }
+    }
+
+    /// Returns the index of the observing player.
+    pub fn observer(&self) -> usize {
+        self.observer
+    }
+}
+
+impl<G: Determinize> Process for IsmctsProcess<G> {
+    type State = InformationSet;
+    type PerChild = IsPerChild<G>;
+    type Update = maxn::Update;
+
+    fn best<'a>(&self, _: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> Option<<Self::PerChild as PerChild>::Key> where Self::PerChild: 'a {
+        edges.max_by_key(|edge| edge.visits()).map(|edge| edge.key())
+    }
+
+    /// Without a determinization every explored move is assumed to be
+    /// available, see `select_open_loop` for the selection during search.
+    fn select<'a>(&self, state: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        maxn::select_uct(edges, state.visits(), |edge| edge.maxn())
+            .map(|edge| SelectResult::Existing(edge.key()))
+            .unwrap_or(SelectResult::None)
+    }
+
+    fn update(&self, state: &Self::State, per_child: &Self::PerChild, update: &Self::Update, _: bool) {
+        state.update();
+        per_child.maxn().update(state.player(), update);
+    }
+}
+
+impl<G: Determinize> OpenLoopProcess for IsmctsProcess<G> {
+    type Simulator = G;
+
+    fn new_state(&self, game: &G) -> Self::State {
+        InformationSet::new(game, self.observer)
+    }
+
+    fn step(&self, game: &G, mv: G::Move) -> G {
+        let mut game = game.clone();
+        game.play(mv);
+        game
+    }
+
+    fn select_open_loop<'a>(&self, _: &Self::State, game: &G, edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        if game.is_terminal() {
+            return SelectResult::None;
+        }
+
+        let mut legal_moves = game.legal_moves();
+        let mut best_edge: Option<(f32, &IsPerChild<G>)> = None;
+
+        for edge in edges {
+            if let Some(i) = legal_moves.iter().position(|&mv| mv == edge.key()) {
+                legal_moves.swap_remove(i);
+                edge.mark_available();
+
+                let uct = edge.uct();
+
+                if best_edge.map(|(best_uct, _)| uct > best_uct).unwrap_or(true) {
+                    best_edge = Some((uct, edge));
+                }
+            }
+        }
+
+        if let Some(&mv) = legal_moves.iter().min() {
+            let per_child = IsPerChild::new(mv);
+            per_child.mark_available();
+
+            SelectResult::Add(per_child)
+        } else if let Some((_, best_edge)) = best_edge {
+            SelectResult::Existing(best_edge.key())
+        } else {
+            SelectResult::None
+        }
+    }
+}
+
+/// Probe the given `search_tree` once through a determinization of `game`,
+/// evaluate the resulting position with a random game, and update the search
+/// tree with the result. Returns the status of the probe.
+///
+/// # Arguments
+///
+/// * `search_tree` - the search tree to probe
+/// * `game` - the actual position at the root of the search tree
+/// * `rng` - the source of randomness
+///
+pub fn search_once<G: Determinize>(search_tree: &Mcts<IsmctsProcess<G>>, game: &G, rng: &mut impl Rng) -> ProbeStatus {
+    let determinization = game.determinize(search_tree.process().observer(), rng);
+    let (trace, status, game) = search_tree.probe_open_loop(&determinization);
+
+    if status == ProbeStatus::Cancelled {
+        search_tree.cancel(trace, false);
+    } else if !trace.is_empty() {
+        let update = GameProcess::<G>::new().rollout(&game, rng);
+
+        search_tree.update_open_loop(trace, &game, update);
+    }
+
+    status
+}
@@ -0,0 +1,47 @@
+use crate::{process, uct};
+use super::Determinize;
+
+/// An information set in an `IsmctsProcess`, which is every position that the
+/// observer of the search tree cannot tell apart.
+pub struct InformationSet {
+    hash: u64,
+    player: usize,
+    uct: uct::State
+}
+
+impl process::State for InformationSet {
+    fn hash(&self) -> Option<u64> {
+        Some(self.hash)
+    }
+}
+
+impl InformationSet {
+    /// Returns the information set of `observer` that contains `game`.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - a position in the information set
+    /// * `observer` - the index of the observing player
+    ///
+    pub fn new<G: Determinize>(game: &G, observer: usize) -> Self {
+        Self {
+            hash: game.information_set(observer),
+            player: game.current_player(),
+            uct: uct::State::new()
+        }
+    }
+
+    /// Returns the index of the player whose turn it is.
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    /// Returns the number of times this information set has been visited.
+    pub fn visits(&self) -> u32 {
+        self.uct.visits()
+    }
+
+    pub(super) fn update(&self) {
+        self.uct.update();
+    }
+}
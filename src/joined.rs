@@ -0,0 +1,100 @@
+use crate::{edge::Edge, node::Node, process::Process, safe_nonnull::SafeNonNull};
+use crossbeam_epoch as epoch;
+use dashmap::{mapref::entry::Entry, DashMap};
+
+/// The nodes and edges of a probe that joined the pending evaluation of the
+/// last edge in the path, from the root towards the leaves.
+pub(super) type JoinedPath<P> = Vec<(SafeNonNull<Node<P>>, SafeNonNull<Edge<P, Node<P>>>)>;
+
+/// Removes the pending mark of a trace that ends at `edge`, when the trace is
+/// cancelled or dropped without being updated.
+pub(super) trait ReleasePending<P: Process, Node>: Sync {
+    fn release(&self, process: &P, edge: SafeNonNull<Edge<P, Node>>);
+}
+
+/// The paths of every probe that joined the pending evaluation of an edge,
+/// keyed by the address of that edge. The pending marks of an edge are only
+/// removed while holding the lock of its entry, which `try_join` also holds
+/// while it checks that the edge is still pending, so a joined path is
+/// always resolved by the last trace that evaluates the edge.
+pub(super) struct Joined<P: Process> {
+    paths: DashMap<usize, Vec<JoinedPath<P>>>
+}
+
+impl<P: Process> ReleasePending<P, Node<P>> for Joined<P> {
+    /// The joined paths are cancelled, see `Process::cancel`, if this was the
+    /// last mark, since no evaluation that they could share is in progress
+    /// anymore.
+    fn release(&self, process: &P, edge: SafeNonNull<Edge<P, Node<P>>>) {
+        let entry = self.paths.entry(edge.as_ptr() as usize);
+        edge.release();
+
+        let joined = match entry {
+            Entry::Occupied(joined) if edge.pending() == 0 => joined.remove(),
+            _ => return
+        };
+        let _pin = epoch::pin();
+
+        for path in joined {
+            for (node, edge) in path.iter().rev() {
+                process.cancel(node.state(), edge.per_child());
+            }
+        }
+    }
+}
+
+impl<P: Process> Joined<P> {
+    pub(super) fn new() -> Self {
+        Self { paths: DashMap::new() }
+    }
+
+    /// Discard every joined path.
+    pub(super) fn clear(&mut self) {
+        self.paths.clear();
+    }
+
+    /// Add the given `path` to the probes that are waiting for the evaluation
+    /// of `edge`, which is the last edge in `path`. Returns false if `edge`
+    /// is no longer pending, in which case nothing is added.
+    ///
+    /// # Arguments
+    ///
+    /// * `edge` - the pending edge to join
+    /// * `path` - the path of the probe that joins it
+    ///
+    pub(super) fn try_join(&self, edge: SafeNonNull<Edge<P, Node<P>>>, path: JoinedPath<P>) -> bool {
+        let entry = self.paths.entry(edge.as_ptr() as usize);
+
+        if !edge.is_pending() {
+            return false;
+        }
+
+        match entry {
+            Entry::Occupied(mut joined) => { joined.get_mut().push(path) },
+            Entry::Vacant(joined) => { joined.insert(vec! [path]); }
+        }
+
+        true
+    }
+
+    /// Remove the pending mark of a trace that ends at `edge` and has been
+    /// updated, and returns the paths of every probe that joined it.
+    pub(super) fn take(&self, edge: SafeNonNull<Edge<P, Node<P>>>) -> Vec<JoinedPath<P>> {
+        let entry = self.paths.entry(edge.as_ptr() as usize);
+        edge.release();
+
+        match entry {
+            Entry::Occupied(joined) => joined.remove(),
+            Entry::Vacant(_) => vec! []
+        }
+    }
+
+    /// Retire `edge`, see `Edge::try_retire`, unless another probe has
+    /// joined its evaluation. Returns true iff the edge was retired.
+    pub(super) fn try_retire(&self, edge: SafeNonNull<Edge<P, Node<P>>>) -> bool {
+        match self.paths.entry(edge.as_ptr() as usize) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(_) => edge.try_retire()
+        }
+    }
+}
@@ -1,17 +1,49 @@
+#[cfg(feature = "async")]
+mod async_search;
+mod cursor;
+mod deterministic;
 mod edge;
+mod ensemble;
+mod joined;
 mod mcts;
+mod metrics;
 mod node;
+mod open_loop;
+mod packed;
 mod path_iter;
+mod pending;
+mod ponder;
 mod probe_status;
+mod progress;
 mod process;
 mod safe_nonnull;
+mod search_rng;
 mod step;
 mod trace;
+pub mod chance;
+pub mod distributed;
+pub mod game;
+pub mod ismcts;
+pub mod maxn;
+pub mod simultaneous;
+pub mod sp;
+pub mod time;
+pub mod transposition;
 pub mod uct;
 
+#[cfg(feature = "async")]
+pub use self::async_search::*;
+pub use self::deterministic::*;
+pub use self::ensemble::*;
 pub use self::mcts::*;
+pub use self::metrics::*;
+pub use self::open_loop::*;
+pub use self::pending::*;
+pub use self::ponder::*;
 pub use self::probe_status::*;
+pub use self::progress::*;
 pub use self::process::*;
+pub use self::search_rng::*;
 pub use self::step::*;
 pub use self::trace::*;
 pub use self::node::*;
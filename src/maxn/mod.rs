@@ -0,0 +1,5 @@
+mod per_child;
+mod update;
+
+pub use self::per_child::*;
+pub use self::update::*;
@@ -0,0 +1,133 @@
+use crate::uct;
+use super::update::Update;
+
+/// The statistics of an edge in a game with any number of players, which
+/// only accumulates the value of the player to move at the parent (max^n).
+pub struct PerChild {
+    uct: uct::PerChild
+}
+
+impl Clone for PerChild {
+    fn clone(&self) -> Self {
+        Self { uct: self.uct.clone() }
+    }
+}
+
+impl Default for PerChild {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PerChild {
+    pub fn new() -> Self {
+        Self { uct: uct::PerChild::new() }
+    }
+
+    /// Accumulate the value of `player`, the player to move at the parent,
+    /// from the given update.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the player to move at the parent
+    /// * `up` - the evaluation for every player
+    ///
+    pub fn update(&self, player: usize, up: &Update) {
+        self.uct.update(&up.uct(player));
+    }
+
+    /// Returns the statistics of the same edge from several search trees
+    /// combined, with the sum of their visits and values.
+    ///
+    /// # Arguments
+    ///
+    /// * `per_children` - the statistics to combine
+    ///
+    pub fn merged<'a>(per_children: impl IntoIterator<Item=&'a Self>) -> Self {
+        Self { uct: uct::PerChild::merged(per_children.into_iter().map(|per_child| &per_child.uct)) }
+    }
+
+    /// Returns the underlying statistics, for the player to move at the
+    /// parent.
+    pub fn stats(&self) -> &uct::PerChild {
+        &self.uct
+    }
+
+    #[inline]
+    pub fn visits(&self) -> u32 {
+        self.uct.visits()
+    }
+
+    /// Returns the average value of this edge for the player to move at the
+    /// parent.
+    #[inline]
+    pub fn value(&self) -> f32 {
+        self.uct.win_rate(self.uct.total_value(), self.uct.visits())
+    }
+
+    #[inline(always)]
+    pub fn uct(&self, total_visits: u32) -> f32 {
+        self.uct.uct(total_visits)
+    }
+
+    /// Returns the upper confidence bound of this edge, with the average
+    /// value replaced by the given `value`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the average value of this edge
+    /// * `total_visits` - the number of visits to the parent
+    ///
+    #[inline(always)]
+    pub fn uct_with_value(&self, value: f32, total_visits: u32) -> f32 {
+        self.uct.uct_with_value(value, total_visits)
+    }
+}
+
+/// Returns the element of `edges` with the highest upper confidence bound,
+/// according to the statistics returned by `per_child`.
+///
+/// # Arguments
+///
+/// * `edges` - the edges to choose from
+/// * `total_visits` - the number of visits to the parent
+/// * `per_child` - returns the statistics of an edge
+///
+pub fn select_uct<'a, E: 'a>(edges: impl Iterator<Item=&'a E>, total_visits: u32, per_child: impl Fn(&E) -> &PerChild) -> Option<&'a E> {
+    let mut best: Option<(f32, &'a E)> = None;
+
+    for edge in edges {
+        let uct = per_child(edge).uct(total_visits);
+
+        if best.map(|(best_uct, _)| uct > best_uct).unwrap_or(true) {
+            best = Some((uct, edge));
+        }
+    }
+
+    best.map(|(_, edge)| edge)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_accumulates_player_to_move() {
+        let per_child = PerChild::new();
+        per_child.update(2, &Update::new([0.0, 0.0, 1.0]));
+        per_child.update(2, &Update::new([1.0, 1.0, 0.0]));
+
+        assert_eq!(per_child.visits(), 2);
+        assert_eq!(per_child.value(), 0.5);
+    }
+
+    #[test]
+    fn select_uct_prefers_higher_value() {
+        let edges = [PerChild::new(), PerChild::new()];
+        edges[0].update(0, &Update::new([0.0, 1.0]));
+        edges[1].update(0, &Update::new([1.0, 0.0]));
+
+        let best = select_uct(edges.iter(), 2, |edge| edge).unwrap();
+        assert!(std::ptr::eq(best, &edges[1]));
+    }
+}
@@ -0,0 +1,86 @@
+use crate::uct;
+use smallvec::SmallVec;
+
+/// An evaluation with one value for every player.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Update {
+    values: SmallVec<[f32; 4]>
+}
+
+impl Update {
+    /// Returns an update with the given `values`, indexed by player.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - the value for each player
+    ///
+    pub fn new(values: impl IntoIterator<Item=f32>) -> Self {
+        Self { values: values.into_iter().collect() }
+    }
+
+    /// Returns an update for a two player zero-sum game, where `player` got
+    /// the given `value` and the opponent got the remainder.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the player that got `value`
+    /// * `value` - the value for `player`
+    ///
+    pub fn zero_sum(player: usize, value: f32) -> Self {
+        let mut values = smallvec::smallvec! [1.0 - value; 2];
+        values[player] = value;
+
+        Self { values }
+    }
+
+    /// Returns the number of players in this update.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns if this update has no players.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the value for the given `player`.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the player to return the value for
+    ///
+    pub fn value(&self, player: usize) -> f32 {
+        self.values[player]
+    }
+
+    /// Returns the value for the given `player` as an `uct::Update`.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the player to return the value for
+    ///
+    pub fn uct(&self, player: usize) -> uct::Update {
+        uct::Update::new(self.value(player))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_is_indexed_by_player() {
+        let update = Update::new([0.25, 0.5, 0.75]);
+
+        assert_eq!(update.len(), 3);
+        assert_eq!(update.value(0), 0.25);
+        assert_eq!(update.value(1), 0.5);
+        assert_eq!(update.value(2), 0.75);
+    }
+
+    #[test]
+    fn zero_sum_gives_remainder_to_opponent() {
+        assert_eq!(Update::zero_sum(0, 0.75), Update::new([0.75, 0.25]));
+        assert_eq!(Update::zero_sum(1, 0.75), Update::new([0.25, 0.75]));
+    }
+}
@@ -1,23 +1,23 @@
-use crate::{node::Node, path_iter::PathIter, probe_status::ProbeStatus, process::{State, Process, PerChild, SelectResult}, safe_nonnull::SafeNonNull, step::Step, trace::Trace};
-use crossbeam_epoch as epoch;
-use dashmap::DashMap;
-use std::{collections::HashSet, ops::DerefMut, rc::Rc};
+use crate::{cursor::Cursor, edge::Edge, joined::{Joined, JoinedPath, ReleasePending}, metrics::{Counters, Metrics}, node::Node, open_loop::OpenLoopProcess, path_iter::PathIter, pending::PendingPolicy, probe_status::ProbeStatus, process::{State, Process, PerChild, SelectResult}, progress::Reporter, safe_nonnull::SafeNonNull, step::Step, trace::Trace, transposition::{DashTable, TableEntry, TranspositionTable}};
+use crossbeam_epoch::{self as epoch, Guard};
+use std::{collections::HashSet, mem, ops::DerefMut, sync::atomic::{AtomicBool, AtomicU64, Ordering}};
 
 pub struct Mcts<P: Process> {
     root: SafeNonNull<Node<P>>,
     process: P,
-    transpositions: DashMap<u64, SafeNonNull<Node<P>>>
+    transpositions: Box<dyn TranspositionTable>,
+    is_stopped: AtomicBool,
+    probes: AtomicU64,
+    counters: Counters,
+    reporter: Option<Reporter<P>>,
+    pending_policy: PendingPolicy,
+    joined: Joined<P>
 }
 
+
 impl<P: Process> Drop for Mcts<P> {
     fn drop(&mut self) {
-        let mut already_dropped = HashSet::with_capacity(self.len());
-        let pin = unsafe { epoch::unprotected() };
-
-        self.root.deref_mut().recursive_drop(pin, &mut already_dropped);
-        if already_dropped.insert(self.root.as_ptr()) {
-            self.root.drop();
-        }
+        Self::drop_tree(self.root, HashSet::with_capacity(self.len()));
     }
 }
 
@@ -31,23 +31,115 @@ impl<P: Process> Mcts<P> {
     /// * `state` - the initial root state
     ///
     pub fn new(process: P, state: P::State) -> Self {
-        let root_hash = state.hash();
+        Self::new_with_table(process, state, DashTable::new())
+    }
+
+    /// Returns a new monte-carlo search tree for the given open-loop
+    /// `process`, whose root is the node reached by `simulator`.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - the monte carlo process to evaluate
+    /// * `simulator` - the simulator state at the root
+    ///
+    pub fn new_open_loop(process: P, simulator: &P::Simulator) -> Self
+        where P: OpenLoopProcess
+    {
+        let state = process.new_state(simulator);
+
+        Self::new(process, state)
+    }
+
+    /// Returns a new monte-carlo search tree for the given `process` and
+    /// initial `state`, which shares nodes between transpositions using the
+    /// given `table`.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - the monte carlo process to evaluate
+    /// * `state` - the initial root state
+    /// * `table` - the transposition table to use
+    ///
+    pub fn new_with_table(process: P, state: P::State, table: impl TranspositionTable + 'static) -> Self {
+        let root_hash = state.hash128();
         let root = SafeNonNull::new(Node::new(state));
-        let transpositions = DashMap::with_capacity(32);
+        let transpositions: Box<dyn TranspositionTable> = Box::new(table);
 
         if let Some(hash) = root_hash {
-            transpositions.insert(hash, root);
+            transpositions.insert(hash, TableEntry::new(root), 0);
         }
 
-        Self { root, process, transpositions }
+        let is_stopped = AtomicBool::new(false);
+        let probes = AtomicU64::new(0);
+        let counters = Counters::default();
+        let reporter = None;
+        let pending_policy = PendingPolicy::default();
+        let joined = Joined::new();
+
+        Counters::add(&counters.nodes_allocated, 1);
+
+        Self { root, process, transpositions, is_stopped, probes, counters, reporter, pending_policy, joined }
     }
 
-    /// Returns the number of entries in the transposition table. This should
-    /// correspond to the number of *unique* nodes in the search tree.
+    /// Returns the number of entries in the transposition table. This is the
+    /// number of *unique* nodes in the search tree only if the table keeps
+    /// every node with a hash, so it is always zero for a `DisabledTable`, and
+    /// at most the capacity of a `FixedTable`. See `Metrics::nodes_allocated`
+    /// for the number of nodes that have been allocated.
     pub fn len(&self) -> usize {
         self.transpositions.len()
     }
 
+    /// Returns the number of times this tree has been probed.
+    pub fn probes(&self) -> u64 {
+        self.probes.load(Ordering::Relaxed)
+    }
+
+    /// Returns a snapshot of the contention and throughput counters of this
+    /// search tree.
+    pub fn metrics(&self) -> Metrics {
+        self.counters.snapshot(self.probes())
+    }
+
+    pub(super) fn counters(&self) -> &Counters {
+        &self.counters
+    }
+
+    /// Attach a `reporter` to this search tree, which is invoked with the
+    /// progress of the search after an `update` whenever it is due.
+    ///
+    /// # Arguments
+    ///
+    /// * `reporter` - the reporter to attach
+    ///
+    pub fn set_reporter(&mut self, mut reporter: Reporter<P>) {
+        reporter.start(self.probes());
+        self.reporter = Some(reporter);
+    }
+
+    /// Set what a probe does when it reaches an edge that is already being
+    /// evaluated by another trace.
+    ///
+    /// # Arguments
+    ///
+    /// * `pending_policy` - the policy to use for pending edges
+    ///
+    pub fn set_pending_policy(&mut self, pending_policy: PendingPolicy) {
+        self.pending_policy = pending_policy;
+        self.joined.clear();
+    }
+
+    /// Returns what a probe does when it reaches an edge that is already
+    /// being evaluated by another trace.
+    pub fn pending_policy(&self) -> PendingPolicy {
+        self.pending_policy
+    }
+
+    /// Returns if pending edges should be hidden from `Process::select`.
+    pub(super) fn skips_pending(&self) -> bool {
+        self.pending_policy == PendingPolicy::Skip
+    }
+
     /// Returns the process being evaluated by this search tree.
     pub fn process(&self) -> &P {
         &self.process
@@ -58,81 +150,479 @@ impl<P: Process> Mcts<P> {
         self.root.state()
     }
 
+    /// Ask every thread that is searching this tree to stop. Any subsequent
+    /// call to `probe` returns `ProbeStatus::Cancelled` until `resume` is
+    /// called.
+    pub fn stop(&self) {
+        self.is_stopped.store(true, Ordering::Release);
+    }
+
+    /// Ask every thread that is searching this tree to stop, like `stop`.
+    /// Returns false if this tree had already been asked to stop.
+    pub(super) fn try_stop(&self) -> bool {
+        !self.is_stopped.swap(true, Ordering::AcqRel)
+    }
+
+    /// Allow this tree to be searched again after a call to `stop`.
+    pub fn resume(&self) {
+        self.is_stopped.store(false, Ordering::Release);
+    }
+
+    /// Returns if this tree has been asked to stop searching.
+    pub fn is_stopped(&self) -> bool {
+        self.is_stopped.load(Ordering::Acquire)
+    }
+
+    /// Drop `root` and every node reachable from it, except the nodes that
+    /// are already in `already_dropped`.
+    fn drop_tree(mut root: SafeNonNull<Node<P>>, mut already_dropped: HashSet<*mut Node<P>>) {
+        let pin = unsafe { epoch::unprotected() };
+
+        root.deref_mut().recursive_drop(pin, &mut already_dropped);
+        if already_dropped.insert(root.as_ptr()) {
+            root.drop();
+        }
+    }
+
+    pub(super) fn root_node(&self) -> &Node<P> {
+        &self.root
+    }
+
+    /// Replace the statistics of every edge of the root with the given
+    /// `per_children`, keeping the subtree of every existing edge.
+    pub(super) fn replace_root_edges(&mut self, per_children: impl IntoIterator<Item=P::PerChild>) {
+        let pin = unsafe { epoch::unprotected() };
+
+        self.root.deref_mut().replace_edges(pin, per_children);
+        self.joined.clear();
+    }
+
+    /// Discard the entire search tree, and start over from the given `state`.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - the new root state
+    ///
+    pub fn reset(&mut self, state: P::State) {
+        let root_hash = state.hash128();
+
+        Self::drop_tree(self.root, HashSet::with_capacity(self.len()));
+        self.root = SafeNonNull::new(Node::new(state));
+        self.transpositions.clear();
+        self.joined.clear();
+        Counters::add(&self.counters.nodes_allocated, 1);
+
+        if let Some(hash) = root_hash {
+            self.transpositions.insert(hash, TableEntry::new(self.root), 0);
+        }
+    }
+
+    /// Make the child reached by following the edge with the given `key` from
+    /// the root the new root of this search tree, keeping its subtree and
+    /// discarding every node that is no longer reachable. Returns true iff
+    /// the edge existed and had been expanded, otherwise the search tree is
+    /// left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key of the edge to follow from the root
+    ///
+    pub fn reroot(&mut self, key: <P::PerChild as PerChild>::Key) -> bool {
+        let pin = unsafe { epoch::unprotected() };
+        let new_root = match self.root.edge(pin, key).and_then(|edge| edge.ptr()) {
+            Some(new_root) => new_root,
+            None => return false
+        };
+
+        let mut reachable = HashSet::with_capacity(self.len());
+        reachable.insert(new_root.as_ptr());
+        new_root.collect_reachable(pin, &mut reachable);
+
+        let old_root = mem::replace(&mut self.root, new_root);
+
+        self.transpositions.retain(&mut |entry| reachable.contains(&entry.node::<P>().as_ptr()));
+        self.joined.clear();
+
+        if !reachable.contains(&old_root.as_ptr()) {
+            Self::drop_tree(old_root, reachable);
+        }
+
+        true
+    }
+
     /// Returns the _best_ sequence of nodes and edges through this search
     /// tree.
     pub fn path<'a>(&'a self) -> impl Iterator<Item=Step<'a, P, Node<P>>> {
         PathIter::new(&self.process, self.root)
     }
 
+    /// Returns up to `n` of the edges of the root, in order of how _good_
+    /// they are according to the process.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - the maximum number of edges to return
+    ///
+    pub fn edges<'a>(&'a self, n: usize) -> Vec<Step<'a, P, Node<P>>> {
+        let pin = epoch::pin();
+        let mut remaining = self.root.edges(&pin).to_vec();
+        let mut edges = Vec::with_capacity(n.min(remaining.len()));
+
+        while edges.len() < n {
+            let best = self.process.best(self.root.state(), remaining.iter().map(|edge| edge.per_child()))
+                .and_then(|key| remaining.iter().position(|edge| edge.key() == key));
+
+            if let Some(i) = best {
+                edges.push(Step::new(&self.process, self.root, remaining.swap_remove(i).key()));
+            } else {
+                break
+            }
+        }
+
+        edges
+    }
+
     /// Returns a `trace` which represents the best path through this tree to
     /// explore at this moment according to the given monte-carlo process and
     /// selection criteria.
     ///
     /// Returns `ProbeStatus::Expanded` if the `trace` contains a previously
     /// unexplored edge as its final step; `ProbeStatus::Busy` if the final edge
-    /// exist but has not yet been expanded yet; and `ProbeStatus::Empty` if
+    /// exist but has not yet been expanded yet; `ProbeStatus::Empty` if
     /// the current selection criterias yielded a terminal node, which has no
-    /// more edges to traverse.
+    /// more edges to traverse; `ProbeStatus::Joined` with an empty `trace` if
+    /// the probe joined the pending evaluation of another trace, see
+    /// `PendingPolicy::Join`; and `ProbeStatus::Cancelled` with the steps
+    /// taken so far if this tree has been asked to `stop`, which should be
+    /// given to `cancel`.
     pub fn probe<'a>(&'a self) -> (Trace<'a, P, Node<P>>, ProbeStatus) {
-        let pin = Rc::new(epoch::pin());
-        let mut trace = Trace::new();
-        let mut curr = self.root;
+        let skip_pending = self.skips_pending();
+
+        self.probe_by(|pin, node, _| node.select(pin, &self.process, skip_pending))
+    }
+
+    /// Returns a trace through this tree that follows the edges returned by
+    /// `select`, which is given the node to select from and its depth.
+    fn probe_by<'a>(&'a self, mut select: impl FnMut(&Guard, &Node<P>, usize) -> SelectResult<P::PerChild>) -> (Trace<'a, P, Node<P>>, ProbeStatus) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("probe").entered();
+        let mut cursor = self.cursor();
 
         loop {
-            match curr.select(&pin, &self.process) {
-                SelectResult::Add(per_child) => {
-                    let next_key = per_child.key();
-                    curr.try_expand(&pin, per_child);
-                    trace.push(&self.process, pin.clone(), curr, next_key);
-
-                    return (trace, ProbeStatus::Expanded)
-                },
-                SelectResult::Existing(next_key) => {
-                    trace.push(&self.process, pin.clone(), curr, next_key);
-
-                    if let Some(next_curr) = curr.edge(&pin, next_key).and_then(|edge| edge.ptr()) {
-                        curr = next_curr;
-                    } else {
-                        return (trace, ProbeStatus::Busy);
-                    }
-                },
-                SelectResult::None => { return (trace, ProbeStatus::Empty) }
+            let depth = cursor.depth();
+
+            if let Some(status) = cursor.step(|pin, node| select(pin, node, depth)) {
+                let trace = cursor.into_trace();
+
+                #[cfg(feature = "tracing")]
+                tracing::trace!(depth = trace.steps().len(), status = ?status, "probe finished");
+
+                return (trace, status)
             }
         }
     }
 
+    /// Returns a cursor at the root of this search tree, which counts as a
+    /// probe.
+    pub(super) fn cursor(&self) -> Cursor<'_, P> {
+        self.probes.fetch_add(1, Ordering::Relaxed);
+
+        Cursor::new(self, self.root)
+    }
+
+    /// Returns a trace through the search tree of an open-loop `process`,
+    /// together with the simulator state reached by replaying every step of
+    /// the trace from the given `simulator`. The status of the probe is the
+    /// same as for `probe`.
+    ///
+    /// # Arguments
+    ///
+    /// * `simulator` - the simulator state at the root
+    ///
+    pub fn probe_open_loop<'a>(&'a self, simulator: &P::Simulator) -> (Trace<'a, P, Node<P>>, ProbeStatus, P::Simulator)
+        where P: OpenLoopProcess
+    {
+        let skip_pending = self.skips_pending();
+        let mut simulators = vec! [simulator.clone()];
+        let (trace, status) = self.probe_by(|pin, node, depth| {
+            // a node is selected from again if its selected edge was retired
+            simulators.truncate(depth + 1);
+
+            let simulator = simulators.last().unwrap();
+            let select_result = self.process.select_open_loop(node.state(), simulator, node.select_edges(pin, skip_pending));
+
+            match &select_result {
+                SelectResult::Add(per_child) => { simulators.push(self.process.step(simulator, per_child.key())) },
+                SelectResult::Existing(key) => { simulators.push(self.process.step(simulator, *key)) },
+                SelectResult::None => {}
+            }
+
+            select_result
+        });
+
+        // a probe that is joined, cancelled, or busy may end with fewer steps
+        // than were replayed, so return the simulator state at its last step
+        let simulator = simulators.swap_remove(trace.steps().len());
+
+        (trace, status, simulator)
+    }
+
+    /// Update the search tree of an open-loop `process` with the evaluation
+    /// `up` of the given `trace`. If the last step of the trace has no
+    /// destination yet, then a node with the state returned by
+    /// `OpenLoopProcess::new_state` for `simulator` is added for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `trace` - the trace to update
+    /// * `simulator` - the simulator state at the end of the trace
+    /// * `up` - the evaluation of the trace
+    ///
+    pub fn update_open_loop(&self, trace: Trace<'_, P, Node<P>>, simulator: &P::Simulator, up: P::Update)
+        where P: OpenLoopProcess
+    {
+        let state = trace.is_pending().then(|| self.process.new_state(simulator));
+
+        self.update(trace, state, up);
+    }
+
     fn insert(&self, trace: &Trace<'_, P, Node<P>>, new_state: P::State) {
         if let Some(last_step) = trace.steps().last() {
-            let new_hash = new_state.hash();
-            let transposed_child = new_hash.and_then(|hash| self.transpositions.get(&hash)).map(|entry| entry.value().clone());
+            self.insert_at(&epoch::pin(), last_step.ptr(), last_step.key(), new_state, trace.steps().len() as u32);
+        }
+    }
+
+    /// Set the destination of the edge with the given `key` in `node` to the
+    /// node of `new_state`, or to an existing node with the same hash if
+    /// `State::same_as` agrees that they are the same state. The `depth` of
+    /// the new node is its distance from the root.
+    pub(super) fn insert_at(&self, pin: &Guard, node: &Node<P>, key: <P::PerChild as PerChild>::Key, new_state: P::State, depth: u32) {
+        let new_hash = new_state.hash128();
+        let transposed_child = new_hash.and_then(|hash| self.transpositions.get(hash)).map(|entry| entry.node::<P>());
+        let is_collision = transposed_child.map(|child| !child.state().same_as(&new_state)).unwrap_or(false);
 
-            if let Some(transposed_child) = transposed_child {
-                let edge = last_step.ptr().edge(last_step.pin(), last_step.key()).unwrap();
+        match transposed_child {
+            Some(transposed_child) if !is_collision => {
+                let edge = node.edge(pin, key).unwrap();
                 edge.try_insert(transposed_child);
-            } else {
+                Counters::add(&self.counters.transposition_hits, 1);
+
+                #[cfg(feature = "tracing")]
+                tracing::trace!(hash = new_hash, "insert transposition hit");
+            },
+            _ => {
                 let new_child = SafeNonNull::new(Node::new(new_state));
+                Counters::add(&self.counters.transposition_misses, 1);
+                Counters::add(&self.counters.nodes_allocated, 1);
+
+                if is_collision {
+                    Counters::add(&self.counters.transposition_collisions, 1);
+
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(hash = new_hash, "insert hash collision");
+                }
+
+                if node.edge(pin, key).map(|edge| edge.try_insert(new_child.clone())).unwrap_or(false) {
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(hash = new_hash, "insert new node");
 
-                if last_step.ptr().edge(last_step.pin(), last_step.key()).map(|edge| edge.try_insert(new_child.clone())).unwrap_or(false) {
-                    if let Some(hash) = new_hash {
-                        self.transpositions.insert(hash, new_child);
+                    // a colliding state keeps its own node, but the existing
+                    // entry in the transposition table is left untouched
+                    if let (Some(hash), false) = (new_hash, is_collision) {
+                        self.transpositions.insert(hash, TableEntry::new(new_child), depth);
                     }
                 } else {
+                    #[cfg(feature = "tracing")]
+                    tracing::trace!(hash = new_hash, "insert lost race");
+
+                    Counters::add(&self.counters.lost_races, 1);
                     new_child.drop();
                 }
             }
         }
     }
 
-    pub fn update(&self, trace: Trace<'_, P, Node<P>>, state: Option<P::State>, up: P::Update) {
+    /// Add the path of the given `trace`, followed by the pending `edge` of
+    /// `node`, to the probes that are waiting for the evaluation of `edge`.
+    /// Returns false if `edge` is no longer pending, in which case nothing
+    /// is added.
+    pub(super) fn try_join(&self, pin: &Guard, trace: &Trace<'_, P, Node<P>>, node: SafeNonNull<Node<P>>, edge: SafeNonNull<Edge<P, Node<P>>>) -> bool {
+        let mut path = trace.steps().iter()
+            .map(|step| (step.node(), step.ptr().edge_ptr(pin, step.key()).unwrap()))
+            .collect::<JoinedPath<P>>();
+        path.push((node, edge));
+
+        self.joined.try_join(edge, path)
+    }
+
+    /// Returns the probes that are waiting for the evaluation of a pending
+    /// edge.
+    pub(super) fn joined(&self) -> &Joined<P> {
+        &self.joined
+    }
+
+    /// Release the pending edge at the end of the given `trace`, and apply
+    /// the update `up` to the path of every probe that joined it.
+    fn release_pending(&self, pin: &Guard, trace: &mut Trace<'_, P, Node<P>>, up: &P::Update) {
+        let edge = match trace.take_pending() {
+            Some((edge, _)) => edge,
+            None => return
+        };
+
+        let joined = if self.pending_policy == PendingPolicy::Join {
+            self.joined.take(edge)
+        } else {
+            edge.release();
+            vec! []
+        };
+
+        for path in joined {
+            for (node, edge) in path.iter().rev() {
+                self.update_edge(pin, node, edge, up);
+            }
+        }
+    }
+
+    fn update_step(&self, pin: &Guard, step: &Step<'_, P, Node<P>>, up: &P::Update) {
+        let node = step.ptr();
+        let edge = node.edge(pin, step.key()).unwrap();
+
+        self.update_edge(pin, node, edge, up);
+    }
+
+    fn update_edge(&self, pin: &Guard, node: &Node<P>, edge: &Edge<P, Node<P>>, up: &P::Update) {
+        let child = edge.ptr();
+
+        node.visit();
+        self.process.update_with_child(node.state(), edge.per_child(), up, child.as_ref().map(|child| child.state()));
+
+        if let Some(outcomes) = node.outcomes() {
+            let explored = node.edges(pin).iter().filter_map(|edge| {
+                outcomes.binary_search_by_key(&edge.key(), |&(key, _)| key)
+                    .ok()
+                    .map(|i| (edge.per_child(), outcomes[i].1))
+            });
+
+            self.process.update_chance(node.state(), explored);
+        }
+    }
+
+    /// Update the search tree with the evaluation `up` of the given `trace`,
+    /// from the last step towards the root. If `state` is given then it is
+    /// added as the destination of the last step. Every probe that joined
+    /// the evaluation of the last step is then updated with `up` as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `trace` - the trace to update
+    /// * `state` - the state at the end of the trace, if it should be added
+    /// * `up` - the evaluation of the trace
+    ///
+    pub fn update(&self, mut trace: Trace<'_, P, Node<P>>, state: Option<P::State>, up: P::Update) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("update", depth = trace.steps().len()).entered();
+
+        if let Some(new_state) = state {
+            self.insert(&trace, new_state)
+        }
+
+        let pin = epoch::pin();
+
+        for step in trace.steps().iter().rev() {
+            self.update_step(&pin, step, &up);
+        }
+
+        self.release_pending(&pin, &mut trace, &up);
+
+        if let Some(reporter) = &self.reporter {
+            reporter.report(self);
+        }
+    }
+
+    /// Abandon the given `trace` without updating the search tree, for example
+    /// because its evaluation failed. `Process::cancel` is called for every
+    /// step, from the last step towards the root. If `remove_expanded` is
+    /// set and the last edge was added by this trace, then that edge is
+    /// removed again unless another trace is also waiting for it, or another
+    /// probe joined it. Probes that joined the last edge are cancelled as well
+    /// once no trace is evaluating it anymore.
+    ///
+    /// # Arguments
+    ///
+    /// * `trace` - the trace to abandon
+    /// * `remove_expanded` - whether to remove the edge added by this trace
+    ///
+    pub fn cancel(&self, mut trace: Trace<'_, P, Node<P>>, remove_expanded: bool) {
+        let pin = epoch::pin();
+
+        for step in trace.steps().iter().rev() {
+            let node = step.ptr();
+            let edge = node.edge(&pin, step.key()).unwrap();
+
+            self.process.cancel(node.state(), edge.per_child());
+        }
+
+        if let Some((edge, is_expanded)) = trace.take_pending() {
+            let is_join = self.pending_policy == PendingPolicy::Join;
+            let is_retired = remove_expanded && is_expanded && if is_join {
+                self.joined.try_retire(edge)
+            } else {
+                edge.try_retire()
+            };
+
+            if is_retired {
+                trace.steps().last().unwrap().ptr().remove(&pin, edge);
+            } else if is_join {
+                self.joined.release(&self.process, edge);
+            } else {
+                edge.release();
+            }
+        }
+    }
+
+    /// Update the search tree with the discounted return of every step in the
+    /// given `trace`, where the return of a step is its own reward plus the
+    /// `discount`-ed return of the step after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `trace` - the trace to update
+    /// * `state` - the state at the end of the trace, if it should be added
+    /// * `rewards` - the reward of every transition in the trace
+    /// * `value` - the estimated return of the state at the end of the trace
+    /// * `discount` - the discount factor of future rewards
+    ///
+    /// # Panics
+    ///
+    /// If `rewards` does not contain exactly one reward per step in `trace`,
+    /// or if the pending policy is `PendingPolicy::Join`, since the rewards
+    /// along the path of a joined probe are not known.
+    ///
+    pub fn update_discounted(&self, mut trace: Trace<'_, P, Node<P>>, state: Option<P::State>, rewards: &[f32], value: f32, discount: f32)
+        where P::Update: From<f32>
+    {
+        assert_eq!(rewards.len(), trace.steps().len(), "expected one reward per step in the trace");
+        assert_ne!(self.pending_policy, PendingPolicy::Join, "discounted updates cannot be shared with joined probes");
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("update", depth = trace.steps().len()).entered();
+
         if let Some(new_state) = state {
             self.insert(&trace, new_state)
         }
 
-        for step in trace.steps() {
-            let node = &step.ptr();
-            let edge = node.edge(step.pin(), step.key()).unwrap();
+        let pin = epoch::pin();
+        let mut ret = value;
+
+        for (step, &reward) in trace.steps().iter().zip(rewards.iter()).rev() {
+            ret = reward + discount * ret;
+            self.update_step(&pin, step, &P::Update::from(ret));
+        }
+
+        self.release_pending(&pin, &mut trace, &P::Update::from(ret));
 
-            self.process.update(node.state(), edge.per_child(), &up, edge.ptr().is_some());
+        if let Some(reporter) = &self.reporter {
+            reporter.report(self);
         }
     }
 }
@@ -0,0 +1,94 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// A snapshot of the contention and throughput counters of a search tree,
+/// which are accumulated since it was created.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// The number of probes that have been started.
+    pub probes: u64,
+
+    /// The number of probes that ended with `ProbeStatus::Busy`.
+    pub busy: u64,
+
+    /// The number of probes that ended with `ProbeStatus::Empty`.
+    pub empty: u64,
+
+    /// The number of probes that waited for the pending evaluation of an
+    /// edge, see `PendingPolicy::Wait`.
+    pub waits: u64,
+
+    /// The number of probes that ended with `ProbeStatus::Joined`.
+    pub joins: u64,
+
+    /// The number of times a compare-exchange had to be retried while adding
+    /// an edge to a node.
+    pub expand_retries: u64,
+
+    /// The number of new children that were dropped because another thread
+    /// inserted a child for the same edge first.
+    pub lost_races: u64,
+
+    /// The number of inserted states that were found in the transposition
+    /// table.
+    pub transposition_hits: u64,
+
+    /// The number of inserted states that were not found in the
+    /// transposition table, including states without a hash.
+    pub transposition_misses: u64,
+
+    /// The number of inserted states whose hash was found in the
+    /// transposition table, but which `State::same_as` rejected as a
+    /// different state. These are also counted as misses.
+    pub transposition_collisions: u64,
+
+    /// The number of nodes that have been allocated, including the root.
+    pub nodes_allocated: u64
+}
+
+/// The live counters behind `Metrics`, which are only ever incremented with
+/// relaxed atomics.
+#[derive(Default)]
+pub(super) struct Counters {
+    pub(super) busy: AtomicU64,
+    pub(super) empty: AtomicU64,
+    pub(super) waits: AtomicU64,
+    pub(super) joins: AtomicU64,
+    pub(super) expand_retries: AtomicU64,
+    pub(super) lost_races: AtomicU64,
+    pub(super) transposition_hits: AtomicU64,
+    pub(super) transposition_misses: AtomicU64,
+    pub(super) transposition_collisions: AtomicU64,
+    pub(super) nodes_allocated: AtomicU64
+}
+
+impl Counters {
+    #[inline]
+    pub(super) fn add(counter: &AtomicU64, n: u64) {
+        if n > 0 {
+            counter.fetch_add(n, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns a snapshot of these counters, with the given number of
+    /// `probes`.
+    ///
+    /// # Arguments
+    ///
+    /// * `probes` - the number of probes that have been started
+    ///
+    pub(super) fn snapshot(&self, probes: u64) -> Metrics {
+        Metrics {
+            probes,
+            busy: self.busy.load(Ordering::Relaxed),
+            empty: self.empty.load(Ordering::Relaxed),
+            waits: self.waits.load(Ordering::Relaxed),
+            joins: self.joins.load(Ordering::Relaxed),
+            expand_retries: self.expand_retries.load(Ordering::Relaxed),
+            lost_races: self.lost_races.load(Ordering::Relaxed),
+            transposition_hits: self.transposition_hits.load(Ordering::Relaxed),
+            transposition_misses: self.transposition_misses.load(Ordering::Relaxed),
+            transposition_collisions: self.transposition_collisions.load(Ordering::Relaxed),
+            nodes_allocated: self.nodes_allocated.load(Ordering::Relaxed)
+        }
+    }
+}
@@ -1,12 +1,17 @@
-use crate::{edge::Edge, process::{PerChild, SelectResult, Process}, safe_nonnull::SafeNonNull};
+use crate::{chance, edge::Edge, search_rng::with_search_rng, process::{PerChild, SelectResult, Process}, safe_nonnull::SafeNonNull};
 use crossbeam_epoch::{Atomic, Owned, Guard};
 use smallvec::*;
-use std::{collections::HashSet, ops::DerefMut, mem, sync::atomic::Ordering};
+use std::{collections::HashSet, ops::DerefMut, mem, sync::{atomic::{AtomicU32, Ordering}, OnceLock}};
+
+/// The key and probability of an outcome of a chance node.
+type Outcome<P> = (<<P as Process>::PerChild as PerChild>::Key, f32);
 
 /// An interior node which represents a game state.
 pub struct Node<P: Process> {
     state: P::State,
-    edges: Atomic<SmallVec<[SafeNonNull<Edge<P, Node<P>>>; 8]>>
+    edges: Atomic<SmallVec<[SafeNonNull<Edge<P, Node<P>>>; 8]>>,
+    visits: AtomicU32,
+    outcomes: OnceLock<Box<[Outcome<P>]>>
 }
 
 impl<P: Process> Drop for Node<P> {
@@ -20,8 +25,36 @@ impl<P: Process> Drop for Node<P> {
 impl<P: Process> Node<P> {
     pub(super) fn new(state: P::State) -> Self {
         let edges = Atomic::new(smallvec! []);
+        let visits = AtomicU32::new(0);
+        let outcomes = OnceLock::new();
+
+        Self { state, edges, visits, outcomes }
+    }
+
+    /// Returns the counter of how many times this node has been updated,
+    /// which lives as long as this node.
+    pub(super) fn visits_counter(&self) -> *const AtomicU32 {
+        &self.visits
+    }
 
-        Self { state, edges }
+    pub(super) fn visit(&self) {
+        self.visits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns how many times this node has been updated.
+    pub(super) fn visits(&self) -> u32 {
+        self.visits.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn add_visits(&self, visits: u32) {
+        self.visits.fetch_add(visits, Ordering::Relaxed);
+    }
+
+    /// Returns the key and probability of every outcome of this chance node,
+    /// sorted by key, or `None` if this node has not been selected from as a
+    /// chance node.
+    pub(super) fn outcomes(&self) -> Option<&[Outcome<P>]> {
+        self.outcomes.get().map(|outcomes| &**outcomes)
     }
 
     #[inline]
@@ -42,6 +75,16 @@ impl<P: Process> Node<P> {
         }
     }
 
+    pub(super) fn collect_reachable(&self, pin: &Guard, reachable: &mut HashSet<*mut Node<P>>) {
+        for edge in self.edges(pin) {
+            if let Some(ptr) = edge.ptr() {
+                if reachable.insert(ptr.as_ptr()) {
+                    ptr.collect_reachable(pin, reachable);
+                }
+            }
+        }
+    }
+
     pub(super) fn best<'g>(&self, pin: &'g Guard, process: &P) -> Option<(<P::PerChild as PerChild>::Key, &'g Edge<P, Node<P>>)> {
         if let Some(key) = process.best(&self.state, self.edges(pin).iter().map(|edge| edge.per_child())) {
             self.edge(pin, key).map(|edge| (key, edge))
@@ -67,9 +110,22 @@ impl<P: Process> Node<P> {
         }).ok()
     }
 
-    pub(super) fn try_expand<'g>(&self, pin: &'g Guard, per_child: P::PerChild) {
+    /// Returns a pointer to the edge with the given `key`, which stays valid
+    /// for as long as the edge is in this node.
+    pub(super) fn edge_ptr(&self, pin: &Guard, key: <P::PerChild as PerChild>::Key) -> Option<SafeNonNull<Edge<P, Node<P>>>> {
+        let edges = self.edges(pin);
+
+        edges.binary_search_by_key(&key, |edge| edge.key()).map(|i| edges[i]).ok()
+    }
+
+    /// Add an edge with the given `per_child` to this node, unless there
+    /// already is an edge with the same key. Returns if the edge was added,
+    /// and the number of times the compare-exchange had to be retried.
+    pub(super) fn try_expand<'g>(&self, pin: &'g Guard, per_child: P::PerChild) -> (bool, u64) {
         let edge = SafeNonNull::new(Edge::new(per_child));
         let mut current = self.edges.load_consume(pin);
+        let mut retries = 0;
+        let is_added;
 
         loop {
             let mut edges = unsafe { current.deref() }.clone();
@@ -77,23 +133,121 @@ impl<P: Process> Node<P> {
             if let Err(at) = edges.binary_search_by_key(&edge.key(), |edge| edge.key()) {
                 edges.insert(at, edge.clone());
             } else {
+                edge.drop();
+                is_added = false;
                 break
             }
 
             match self.edges.compare_exchange_weak(current, Owned::new(edges), Ordering::AcqRel, Ordering::Relaxed, pin) {
                 Ok(_) => {
                     unsafe { pin.defer_destroy(current) };
+                    is_added = true;
                     break
                 },
                 Err(err) => {
                     current = err.current;
+                    retries += 1;
                 }
             }
         }
+
+        #[cfg(feature = "tracing")]
+        if retries > 0 {
+            tracing::trace!(retries, "try_expand retried compare exchange");
+        }
+
+        (is_added, retries)
     }
 
-    pub(super) fn select<'g>(&self, pin: &'g Guard, process: &P) -> SelectResult<P::PerChild> {
-        process.select(&self.state, self.edges(pin).iter().map(|edge| edge.per_child()))
+    /// Remove the given `edge`, which must have been retired, from this node.
+    /// The edge is destroyed once no thread can still observe it.
+    pub(super) fn remove(&self, pin: &Guard, edge: SafeNonNull<Edge<P, Node<P>>>) {
+        let mut current = self.edges.load_consume(pin);
+
+        loop {
+            let mut edges = unsafe { current.deref() }.clone();
+
+            match edges.iter().position(|other| *other == edge) {
+                Some(at) => { edges.remove(at); },
+                None => return
+            }
+
+            match self.edges.compare_exchange_weak(current, Owned::new(edges), Ordering::AcqRel, Ordering::Relaxed, pin) {
+                Ok(_) => {
+                    unsafe {
+                        pin.defer_destroy(current);
+                        pin.defer_unchecked(move || edge.drop());
+                    }
+
+                    return
+                },
+                Err(err) => {
+                    current = err.current;
+                }
+            }
+        }
+    }
+
+    /// Replace the edges of this node with new edges for the given
+    /// `per_children`, which keep the destination of any existing edge with
+    /// the same key. Every existing key must be present in `per_children`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` -
+    /// * `per_children` - the statistics of the new edges
+    ///
+    pub(super) fn replace_edges(&mut self, pin: &Guard, per_children: impl IntoIterator<Item=P::PerChild>) {
+        let old_edges = self.edges(pin);
+        let mut new_edges = per_children.into_iter().map(|per_child| {
+            let edge = Edge::new(per_child);
+
+            if let Some(ptr) = old_edges.iter().find(|old_edge| old_edge.key() == edge.key()).and_then(|old_edge| old_edge.ptr()) {
+                edge.try_insert(ptr);
+            }
+
+            SafeNonNull::new(edge)
+        }).collect::<SmallVec<[_; 8]>>();
+        new_edges.sort_by_key(|edge| edge.key());
+
+        assert!(old_edges.iter().all(|old_edge| new_edges.binary_search_by_key(&old_edge.key(), |edge| edge.key()).is_ok()));
+
+        for old_edge in old_edges {
+            old_edge.drop();
+        }
+
+        unsafe {
+            drop(mem::replace(&mut self.edges, Atomic::new(new_edges)).into_owned());
+        }
+    }
+
+    /// Returns the statistics of the edges of this node that may be passed to
+    /// `Process::select`, which excludes every pending edge if
+    /// `skip_pending` is set.
+    pub(super) fn select_edges<'g>(&self, pin: &'g Guard, skip_pending: bool) -> impl Iterator<Item=&'g P::PerChild> where P: 'g {
+        self.edges(pin).iter()
+            .filter(move |edge| !skip_pending || !edge.is_pending())
+            .map(|edge| edge.per_child())
+    }
+
+    pub(super) fn select<'g>(&self, pin: &'g Guard, process: &P, skip_pending: bool) -> SelectResult<P::PerChild> {
+        if let Some(outcomes) = process.outcomes(&self.state) {
+            self.outcomes.get_or_init(|| {
+                let mut keys = outcomes.iter().map(|(outcome, probability)| (outcome.key(), *probability)).collect::<Box<[_]>>();
+                keys.sort_by_key(|&(key, _)| key);
+                keys
+            });
+
+            match with_search_rng(|mut rng| chance::sample(outcomes, &mut rng)) {
+                Some(per_child) if self.edge(pin, per_child.key()).is_some() => SelectResult::Existing(per_child.key()),
+                Some(per_child) => SelectResult::Add(per_child),
+                None => SelectResult::None
+            }
+        } else if skip_pending {
+            process.select_skipping(&self.state, self.edges(pin).iter().map(|edge| (edge.per_child(), edge.is_pending())))
+        } else {
+            process.select(&self.state, self.select_edges(pin, false))
+        }
     }
 
     pub(super) fn map<'g, T>(&self, pin: &'g Guard, key: <P::PerChild as PerChild>::Key, f: impl FnOnce(&P::State, &Edge<P, Node<P>>, &P::PerChild) -> T) -> T {
@@ -124,6 +278,29 @@ mod tests {
         assert_eq!(node.len(&pin), 1);
     }
 
+    #[test]
+    fn try_expand_existing_key_is_not_added() {
+        let pin = epoch::pin();
+        let node: Node<FakeProcess> = Node::new(FakeState::new());
+
+        assert_eq!(node.try_expand(&pin, FakePerChild::new(0)), (true, 0));
+        assert_eq!(node.try_expand(&pin, FakePerChild::new(0)), (false, 0));
+        assert_eq!(node.len(&pin), 1);
+    }
+
+    #[test]
+    fn remove_only_removes_the_given_edge() {
+        let pin = epoch::pin();
+        let node: Node<FakeProcess> = Node::new(FakeState::new());
+        node.try_expand(&pin, FakePerChild::new(0));
+        node.try_expand(&pin, FakePerChild::new(1));
+
+        node.remove(&pin, node.edge_ptr(&pin, 0).unwrap());
+        assert_eq!(node.len(&pin), 1);
+        assert!(node.edge(&pin, 0).is_none());
+        assert!(node.edge(&pin, 1).is_some());
+    }
+
     #[test]
     fn map_gets_the_correct_edge() {
         let pin = epoch::pin();
@@ -162,6 +339,6 @@ mod tests {
         let pin = epoch::pin();
         let process = FakeProcess::new(0, 1);
         let node: Node<FakeProcess> = Node::new(FakeState::new());
-        assert_eq!(node.select(&pin, &process), SelectResult::Add(FakePerChild::new(1)));
+        assert_eq!(node.select(&pin, &process, false), SelectResult::Add(FakePerChild::new(1)));
     }
 }
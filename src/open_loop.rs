@@ -0,0 +1,51 @@
+use crate::process::{PerChild, Process, SelectResult};
+
+/// A monte-carlo process over a stochastic simulator, where the nodes of the
+/// search tree are identified only by the sequence of actions from the root.
+/// The `State` of each node then holds the statistics of that node, averaged
+/// over every simulator state the action sequence has led to, and should
+/// return `None` from `hash` so that nodes are never shared between different
+/// action sequences.
+///
+/// The simulator state is never stored in the search tree, but regenerated by
+/// `Mcts::probe_open_loop`, which replays every action of the trace with
+/// `step`. New nodes are added by `Mcts::update_open_loop` with the state
+/// returned by `new_state`.
+pub trait OpenLoopProcess: Process {
+    type Simulator: Clone;
+
+    /// Returns the state of a new node that is first reached by `simulator`.
+    /// The same node is reached by every simulator state of its action
+    /// sequence, so this should only hold the statistics of the node and
+    /// whatever those simulator states have in common.
+    ///
+    /// # Arguments
+    ///
+    /// * `simulator` -
+    ///
+    fn new_state(&self, simulator: &Self::Simulator) -> Self::State;
+
+    /// Returns the simulator state after playing `key` in `simulator`, which
+    /// may differ between calls if the simulator is stochastic.
+    ///
+    /// # Arguments
+    ///
+    /// * `simulator` -
+    /// * `key` -
+    ///
+    fn step(&self, simulator: &Self::Simulator, key: <Self::PerChild as PerChild>::Key) -> Self::Simulator;
+
+    /// Returns the edge to be explored during search for a given `state`,
+    /// current `simulator` state, and set of already evaluated `edges`. Calls
+    /// `select` by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` -
+    /// * `simulator` -
+    /// * `edges` -
+    ///
+    fn select_open_loop<'a>(&self, state: &Self::State, _simulator: &Self::Simulator, edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        self.select(state, edges)
+    }
+}
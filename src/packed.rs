@@ -0,0 +1,29 @@
+/// Pack a sum of `value`s and the number of `total_visits` into a single
+/// word, so that both can be updated together with one compare-exchange.
+#[inline]
+pub(super) fn pack(value: f32, total_visits: u32) -> u64 {
+    ((f32::to_bits(value) as u64) << 32) | total_visits as u64
+}
+
+/// Returns the sum of values and the number of visits in a word returned by
+/// `pack`.
+#[inline]
+pub(super) fn unpack(packed: u64) -> (f32, u32) {
+    let value = (packed >> 32) as u32;
+    let total_visits = (packed & 0xffffffff) as u32;
+
+    (f32::from_bits(value), total_visits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpack_pack() {
+        let (value, visits) = unpack(pack(3.14, 7));
+
+        assert_eq!(value, 3.14);
+        assert_eq!(visits, 7);
+    }
+}
@@ -1,23 +1,22 @@
 use crate::{node::Node, process::Process, safe_nonnull::SafeNonNull, step::Step};
 use crossbeam_epoch as epoch;
-use std::rc::Rc;
 
 pub struct PathIter<'a, P: Process> {
     process: &'a P,
-    pin: Rc<epoch::Guard>,
+    pin: epoch::Guard,
     current: Option<SafeNonNull<Node<P>>>
 }
 
 impl<'a, P: Process> PathIter<'a, P> {
     pub(super) fn new(process: &'a P, starting_point: SafeNonNull<Node<P>>) -> Self {
         let current = Some(starting_point);
-        let pin = Rc::new(epoch::pin());
+        let pin = epoch::pin();
 
         Self { process, pin, current }
     }
 
     pub(super) fn pin(&self) -> &epoch::Guard {
-        self.pin.as_ref()
+        &self.pin
     }
 }
 
@@ -30,7 +29,7 @@ impl<'a, P: Process> Iterator for PathIter<'a, P> {
         if let Some((key, edge)) = curr.and_then(|node| node.best(self.pin(), self.process)) {
             self.current = edge.ptr();
 
-            Some(Step::new(self.process, self.pin.clone(), curr.unwrap(), key))
+            Some(Step::new(self.process, curr.unwrap(), key))
         } else {
             None
         }
@@ -0,0 +1,28 @@
+/// What a probe does when it reaches an edge that is already being evaluated
+/// by another trace, i.e. an edge without a child that is marked as pending
+/// by a trace that has not been updated, cancelled, or dropped yet.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PendingPolicy {
+    /// End the probe with `ProbeStatus::Busy`, so that the edge is evaluated
+    /// once more by the caller.
+    #[default]
+    Busy,
+
+    /// Wait until the pending evaluation has been added to the search tree,
+    /// and then continue the probe through the new child. If the evaluation
+    /// finishes without adding a child then the probe ends with
+    /// `ProbeStatus::Busy`. A thread must not wait for an edge it is itself
+    /// evaluating, so this cannot be used with `AsyncSearch`.
+    Wait,
+
+    /// Hide every pending edge from `Process::select`, so that the next best
+    /// edge is picked instead, see `Process::select_skipping`. If the selected edge still turns out to be
+    /// pending then the probe ends with `ProbeStatus::Busy`.
+    Skip,
+
+    /// End the probe with `ProbeStatus::Joined` and an empty trace. The
+    /// update of the pending evaluation is then also applied to the path of
+    /// this probe, as if it had been evaluated twice. This cannot be used
+    /// together with `Mcts::update_discounted`.
+    Join
+}
@@ -0,0 +1,90 @@
+use crate::{mcts::Mcts, process::{PerChild, Process}};
+use std::{sync::Arc, thread::{self, JoinHandle}};
+
+/// A search that keeps running in the background on the opponent's time,
+/// until the opponent plays a move.
+pub struct Ponder<P: Process> {
+    search_tree: Arc<Mcts<P>>,
+    workers: Vec<JoinHandle<()>>
+}
+
+impl<P: Process> Drop for Ponder<P> {
+    fn drop(&mut self) {
+        // a tree that was already stopped, before or during the ponder, is
+        // left stopped
+        let is_stopping = self.search_tree.try_stop();
+
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+
+        if is_stopping {
+            self.search_tree.resume();
+        }
+    }
+}
+
+impl<P: Process + Send + Sync + 'static> Ponder<P> {
+    /// Returns a ponder that searches the given `search_tree` on `num_threads`
+    /// background threads, each of which repeatedly calls `worker` until the
+    /// ponder is stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_tree` - the search tree to keep searching
+    /// * `num_threads` - the number of background threads
+    /// * `worker` - a single probe, evaluate, and update of the search tree
+    ///
+    pub fn start(search_tree: Mcts<P>, num_threads: usize, worker: impl Fn(&Mcts<P>) + Send + Sync + 'static) -> Self {
+        let search_tree = Arc::new(search_tree);
+        let worker = Arc::new(worker);
+        let workers = (0..num_threads).map(|_| {
+            let search_tree = search_tree.clone();
+            let worker = worker.clone();
+
+            thread::spawn(move || {
+                while !search_tree.is_stopped() {
+                    worker(&search_tree);
+                }
+            })
+        }).collect();
+
+        Self { search_tree, workers }
+    }
+}
+
+impl<P: Process> Ponder<P> {
+    /// Returns the search tree that is being searched in the background.
+    pub fn search_tree(&self) -> &Mcts<P> {
+        &self.search_tree
+    }
+
+    /// Stop all background threads, and returns the search tree.
+    pub fn stop(self) -> Mcts<P> {
+        let search_tree = self.search_tree.clone();
+
+        drop(self);
+        Arc::try_unwrap(search_tree).map_err(|_| ()).unwrap()
+    }
+
+    /// Stop all background threads, and returns the search tree after the
+    /// opponent played the edge with the given `key`. If the move has been
+    /// searched (a _ponder hit_) then the searched subtree is kept, otherwise
+    /// (a _ponder miss_) the search tree is discarded and started over from
+    /// the given `state`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the move played by the opponent
+    /// * `state` - the state after the opponent played `key`
+    ///
+    pub fn play(self, key: <P::PerChild as PerChild>::Key, state: P::State) -> Mcts<P> {
+        let mut search_tree = self.stop();
+
+        if !search_tree.reroot(key) {
+            search_tree.reset(state);
+        }
+
+        search_tree
+    }
+}
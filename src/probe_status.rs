@@ -1,7 +1,9 @@
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ProbeStatus {
     Busy,
+    Cancelled,
     Empty,
     Existing(usize),
-    Expanded
+    Expanded,
+    Joined
 }
@@ -1,5 +1,24 @@
 pub trait State {
     fn hash(&self) -> Option<u64>;
+
+    /// Returns the 128-bit hash of this state, which is used to find
+    /// transpositions instead of `hash` when the latter is too narrow to
+    /// avoid collisions. The default widens `hash`.
+    fn hash128(&self) -> Option<u128> {
+        self.hash().map(u128::from)
+    }
+
+    /// Returns if this state is the same as `other`, which has the same hash.
+    /// This is used to verify transpositions, and a collision is given its
+    /// own node. The default trusts the hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the state with the same hash as this state
+    ///
+    fn same_as(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
 pub trait PerChild {
@@ -41,6 +60,22 @@ pub trait Process {
     ///
     fn select<'a>(&self, state: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a;
 
+    /// Returns the edge to be explored during search for a given `state`,
+    /// like `select`, when pending edges are skipped, see
+    /// `PendingPolicy::Skip`. The pending edges are given in `edges`, so
+    /// that they are not mistaken for unexplored edges, but must not be
+    /// returned. Calls `select` with every edge that is not pending by
+    /// default.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` -
+    /// * `edges` - every explored edge, and whether it is pending
+    ///
+    fn select_skipping<'a>(&self, state: &Self::State, edges: impl Iterator<Item=(&'a Self::PerChild, bool)>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        self.select(state, edges.filter(|&(_, is_pending)| !is_pending).map(|(edge, _)| edge))
+    }
+
     /// Update the statistics for this `state` and `per_child` based on a
     /// user-provided evaluation `update`.
     ///
@@ -52,6 +87,63 @@ pub trait Process {
     /// * `is_expanded` -
     ///
     fn update(&self, state: &Self::State, per_child: &Self::PerChild, update: &Self::Update, is_expanded: bool);
+
+    /// Returns every possible outcome of the given `state` together with its
+    /// probability if it is a chance node, or `None` if the edge to explore
+    /// should be chosen by `select`. The outcome to explore during search is
+    /// sampled according to the probabilities. The probabilities are
+    /// remembered by the search tree, so they must not change for a state.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` -
+    ///
+    fn outcomes(&self, _state: &Self::State) -> Option<Vec<(Self::PerChild, f32)>> {
+        None
+    }
+
+    /// Update the statistics of the chance node `state` after one of its
+    /// outcomes has been updated, for example with the expected value over
+    /// all explored `outcomes`.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` -
+    /// * `outcomes` - all explored outcomes and their probabilities
+    ///
+    fn update_chance<'a>(&self, _state: &Self::State, _outcomes: impl Iterator<Item=(&'a Self::PerChild, f32)>) where Self::PerChild: 'a {
+        // pass
+    }
+
+    /// Update the statistics for this `state` and `per_child`, where `child`
+    /// is the state the edge leads to if it has been expanded. Since the
+    /// children of a trace are updated before their parents this can be used
+    /// to back up statistics from the `child`, such as the expected value of
+    /// a chance node. Calls `update` by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` -
+    /// * `per_child` -
+    /// * `update` -
+    /// * `child` -
+    ///
+    fn update_with_child(&self, state: &Self::State, per_child: &Self::PerChild, update: &Self::Update, child: Option<&Self::State>) {
+        self.update(state, per_child, update, child.is_some())
+    }
+
+    /// Roll back any bookkeeping that was done for this `state` and
+    /// `per_child` while probing, such as a virtual loss, for a trace that is
+    /// cancelled instead of updated. Does nothing by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` -
+    /// * `per_child` -
+    ///
+    fn cancel(&self, _state: &Self::State, _per_child: &Self::PerChild) {
+        // pass
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,166 @@
+use crate::{mcts::Mcts, node::Node, process::{PerChild, Process}, step::Step};
+use std::{sync::Mutex, time::{Duration, Instant}};
+
+/// How often a `Reporter` should be invoked.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Interval {
+    Probes(u64),
+    Time(Duration)
+}
+
+/// A snapshot of the progress of a search.
+pub struct Progress<'a, P: Process> {
+    probes: u64,
+    elapsed: Duration,
+    nodes: usize,
+    principal_variation: Vec<<P::PerChild as PerChild>::Key>,
+    edges: Vec<Step<'a, P, Node<P>>>
+}
+
+impl<'a, P: Process> Progress<'a, P> {
+    /// Returns the number of probes since the reporter was attached.
+    pub fn probes(&self) -> u64 {
+        self.probes
+    }
+
+    /// Returns the time elapsed since the reporter was attached.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the average number of probes per second since the reporter
+    /// was attached.
+    pub fn probes_per_second(&self) -> f64 {
+        let elapsed = self.elapsed.as_secs_f64();
+
+        if elapsed > 0.0 {
+            self.probes as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the number of unique nodes in the search tree.
+    pub fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    /// Returns the keys of the current _best_ sequence through the search
+    /// tree.
+    pub fn principal_variation(&self) -> &[<P::PerChild as PerChild>::Key] {
+        &self.principal_variation
+    }
+
+    /// Returns the _best_ edges of the root, in order of preference.
+    pub fn edges(&self) -> &[Step<'a, P, Node<P>>] {
+        &self.edges
+    }
+}
+
+type Callback<P> = Box<dyn Fn(&Progress<'_, P>) + Send + Sync>;
+
+/// A callback that is invoked with the `Progress` of a search at a regular
+/// interval.
+pub struct Reporter<P: Process> {
+    interval: Interval,
+    num_edges: usize,
+    callback: Callback<P>,
+    started: (Instant, u64),
+    last_report: Mutex<(Instant, u64)>
+}
+
+impl<P: Process> Reporter<P> {
+    /// Returns a reporter that calls `callback` every `interval`.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - how often to call `callback`
+    /// * `callback` - the function to call with the progress of the search
+    ///
+    pub fn new(interval: Interval, callback: impl Fn(&Progress<'_, P>) + Send + Sync + 'static) -> Self {
+        let now = Instant::now();
+
+        Self {
+            interval,
+            num_edges: 5,
+            callback: Box::new(callback),
+            started: (now, 0),
+            last_report: Mutex::new((now, 0))
+        }
+    }
+
+    /// Returns this reporter with the `num_edges` best root edges included
+    /// in every report.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_edges` - the number of root edges to report
+    ///
+    pub fn with_edges(self, num_edges: usize) -> Self {
+        Self { num_edges, ..self }
+    }
+
+    pub(super) fn start(&mut self, probes: u64) {
+        let now = Instant::now();
+
+        self.started = (now, probes);
+        self.last_report = Mutex::new((now, probes));
+    }
+
+    fn is_due(&self, last_report: &(Instant, u64), now: Instant, probes: u64) -> bool {
+        match self.interval {
+            Interval::Probes(n) => probes.saturating_sub(last_report.1) >= n,
+            Interval::Time(duration) => now.saturating_duration_since(last_report.0) >= duration
+        }
+    }
+
+    /// Call the callback with the current progress of `search_tree` if it is
+    /// due. If another thread is already reporting then this does nothing.
+    pub(super) fn report(&self, search_tree: &Mcts<P>) {
+        if let Ok(mut last_report) = self.last_report.try_lock() {
+            let now = Instant::now();
+            let probes = search_tree.probes();
+
+            if self.is_due(&last_report, now, probes) {
+                *last_report = (now, probes);
+
+                (self.callback)(&Progress {
+                    probes: probes - self.started.1,
+                    elapsed: now.saturating_duration_since(self.started.0),
+                    nodes: search_tree.len(),
+                    principal_variation: search_tree.path().map(|step| step.key()).collect(),
+                    edges: search_tree.edges(self.num_edges)
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FakeProcess, FakeState};
+    use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+    use super::*;
+
+    #[test]
+    fn report_every_other_probe() {
+        let num_reports = Arc::new(AtomicUsize::new(0));
+        let mut search_tree = Mcts::new(FakeProcess::new(1, 1), FakeState::new());
+        search_tree.set_reporter(Reporter::new(Interval::Probes(2), {
+            let num_reports = num_reports.clone();
+
+            move |progress| {
+                assert_eq!(progress.principal_variation(), &[1]);
+                assert_eq!(progress.edges().len(), 1);
+                num_reports.fetch_add(1, Ordering::Relaxed);
+            }
+        }));
+
+        for _ in 0..10 {
+            let (trace, _) = search_tree.probe();
+            search_tree.update(trace, None, ());
+        }
+
+        assert_eq!(num_reports.load(Ordering::Relaxed), 5);
+    }
+}
@@ -0,0 +1,128 @@
+use rand::{rngs::StdRng, RngCore};
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static SEARCH_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+    static IS_SEEDED_IN_USE: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The seeded generator while it is lent out by `with_search_rng`, which is
+/// put back when dropped, even if the caller panics.
+struct Lent {
+    rng: Option<StdRng>
+}
+
+impl Drop for Lent {
+    fn drop(&mut self) {
+        SEARCH_RNG.with(|search_rng| *search_rng.borrow_mut() = self.rng.take());
+        IS_SEEDED_IN_USE.with(|is_in_use| is_in_use.set(false));
+    }
+}
+
+/// Call `f` with the source of randomness that a process should use during
+/// `Mcts::probe`, for example to break ties or to sample outcomes. This is a
+/// seeded generator when searching with `Deterministic`, and `thread_rng`
+/// otherwise.
+///
+/// Calls must not be nested while the generator is seeded, since the inner
+/// call could only fall back to `thread_rng`, which would silently break
+/// the reproducibility of the search. This is checked in debug builds.
+///
+/// # Arguments
+///
+/// * `f` - the function to call with the generator
+///
+pub fn with_search_rng<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    let seeded = SEARCH_RNG.with(|search_rng| search_rng.borrow_mut().take());
+
+    match seeded {
+        Some(rng) => {
+            let mut lent = Lent { rng: Some(rng) };
+            IS_SEEDED_IN_USE.with(|is_in_use| is_in_use.set(true));
+
+            f(lent.rng.as_mut().unwrap())
+        },
+        None => {
+            debug_assert!(
+                !IS_SEEDED_IN_USE.with(|is_in_use| is_in_use.get()),
+                "with_search_rng must not be nested while the generator is seeded"
+            );
+
+            f(&mut rand::thread_rng())
+        }
+    }
+}
+
+/// Replace the generator returned by `with_search_rng` on this thread, and
+/// returns the previous one.
+pub(super) fn replace_search_rng(rng: Option<StdRng>) -> Option<StdRng> {
+    SEARCH_RNG.with(|search_rng| search_rng.replace(rng))
+}
+
+/// Seeds the generator returned by `with_search_rng` on this thread until it
+/// is dropped, after which the previous generator is restored, even if the
+/// thread panics in the meantime.
+pub(super) struct SeededSearchRng {
+    previous: Option<StdRng>
+}
+
+impl Drop for SeededSearchRng {
+    fn drop(&mut self) {
+        replace_search_rng(self.previous.take());
+    }
+}
+
+impl SeededSearchRng {
+    pub(super) fn new(rng: StdRng) -> Self {
+        Self { previous: replace_search_rng(Some(rng)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use std::panic;
+    use super::*;
+
+    #[test]
+    fn seeded_is_reproducible() {
+        let draw = || with_search_rng(|rng| rng.gen::<u64>());
+
+        replace_search_rng(Some(StdRng::seed_from_u64(7)));
+        let first = (draw(), draw());
+        replace_search_rng(Some(StdRng::seed_from_u64(7)));
+        let second = (draw(), draw());
+        replace_search_rng(None);
+
+        assert_eq!(first, second);
+        assert_ne!(first.0, first.1);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "must not be nested")]
+    fn nested_seeded_calls_panic() {
+        let _seeded = SeededSearchRng::new(StdRng::seed_from_u64(7));
+
+        with_search_rng(|rng| rng.gen::<u32>() ^ with_search_rng(|rng| rng.gen::<u32>()));
+    }
+
+    #[test]
+    fn seeded_is_restored_after_panic() {
+        let _seeded = SeededSearchRng::new(StdRng::seed_from_u64(7));
+        let result = panic::catch_unwind(|| with_search_rng(|_| panic!("evaluation failed")));
+
+        assert!(result.is_err());
+        assert!(replace_search_rng(None).is_some());
+    }
+
+    #[test]
+    fn seeded_restores_previous() {
+        replace_search_rng(Some(StdRng::seed_from_u64(7)));
+        drop(SeededSearchRng::new(StdRng::seed_from_u64(8)));
+
+        let mut expected = StdRng::seed_from_u64(7);
+        assert_eq!(with_search_rng(|rng| rng.gen::<u64>()), expected.gen::<u64>());
+        replace_search_rng(None);
+    }
+}
@@ -0,0 +1,63 @@
+/// The rules of a game where every player chooses their move at the same
+/// time, from which a complete monte-carlo process can be derived using
+/// `SimultaneousProcess`.
+pub trait SimultaneousGame: Clone + 'static {
+    type Move: Copy + Ord;
+
+    /// Returns the number of players in this game.
+    fn num_players(&self) -> usize {
+        2
+    }
+
+    /// Returns all moves that are legal for `player` to play in this
+    /// position.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the index of the player
+    ///
+    fn legal_moves(&self, player: usize) -> Vec<Self::Move>;
+
+    /// Play the given joint move, which contains one legal move per player.
+    ///
+    /// # Arguments
+    ///
+    /// * `joint_move` - the move of every player, indexed by player
+    ///
+    fn play(&mut self, joint_move: &[Self::Move]);
+
+    /// Returns if the game is over.
+    fn is_terminal(&self) -> bool;
+
+    /// Returns the reward for the given `player` in a terminal position,
+    /// between `0.0` (a loss) and `1.0` (a win).
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the index of the player
+    ///
+    fn reward(&self, player: usize) -> f32;
+
+    /// Returns the hash of this position, if positions should be shared
+    /// between transpositions.
+    fn hash(&self) -> Option<u64> {
+        None
+    }
+
+    /// Returns the 128-bit hash of this position, if positions should be
+    /// shared between transpositions. The default widens `hash`.
+    fn hash128(&self) -> Option<u128> {
+        self.hash().map(u128::from)
+    }
+
+    /// Returns if this position is the same as `other`, which has the same
+    /// hash. The default trusts the hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the position with the same hash as this position
+    ///
+    fn same_as(&self, _other: &Self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{process, uct};
+use smallvec::SmallVec;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// The statistics of a joint move in a `SimultaneousProcess`, where the key
+/// identifies the move of every player.
+pub struct JointPerChild {
+    key: u64,
+    visits: AtomicU32,
+    probabilities: SmallVec<[AtomicU32; 4]>
+}
+
+impl process::PerChild for JointPerChild {
+    type Key = u64;
+
+    fn key(&self) -> Self::Key {
+        self.key
+    }
+}
+
+impl JointPerChild {
+    pub fn new(key: u64) -> Self {
+        Self { key, visits: AtomicU32::new(0), probabilities: SmallVec::new() }
+    }
+
+    /// Returns this joint move, where every player chose their move with the
+    /// given probability.
+    ///
+    /// # Arguments
+    ///
+    /// * `probabilities` - the probability of the move of every player
+    ///
+    pub(super) fn with_probabilities(self, probabilities: &[f32]) -> Self {
+        let probabilities = probabilities.iter().map(|&probability| AtomicU32::new(f32::to_bits(probability))).collect();
+
+        Self { probabilities, ..self }
+    }
+
+    /// Returns the number of times this joint move has been visited.
+    pub fn visits(&self) -> u32 {
+        self.visits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the probability that the given `player` chose their move the
+    /// last time this joint move was selected, or `1.0` if the move was not
+    /// sampled. If several probes select this joint move at the same time
+    /// then each of them sees the probabilities of the latest selection.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the index of the player
+    ///
+    pub fn probability(&self, player: usize) -> f32 {
+        self.probabilities.get(player).map(|probability| f32::from_bits(probability.load(Ordering::Relaxed))).unwrap_or(1.0)
+    }
+
+    /// Set the probability that every player chose their move, when this
+    /// joint move is selected again.
+    ///
+    /// # Arguments
+    ///
+    /// * `probabilities` - the probability of the move of every player
+    ///
+    pub(super) fn set_probabilities(&self, probabilities: &[f32]) {
+        for (prev_probability, &probability) in self.probabilities.iter().zip(probabilities.iter()) {
+            prev_probability.store(f32::to_bits(probability), Ordering::Relaxed);
+        }
+    }
+
+    pub(super) fn update(&self) {
+        self.visits.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// The marginal statistics of a single move of a single player, which are
+/// updated whenever a joint move that contains it is visited.
+pub struct MoveStats {
+    uct: uct::PerChild,
+    estimate: AtomicU32
+}
+
+impl Default for MoveStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MoveStats {
+    pub fn new() -> Self {
+        Self { uct: uct::PerChild::new(), estimate: AtomicU32::new(f32::to_bits(0.0)) }
+    }
+
+    /// Returns the number of times this move has been visited.
+    pub fn visits(&self) -> u32 {
+        self.uct.visits()
+    }
+
+    /// Returns the average reward of this move, for the player that plays it.
+    pub fn value(&self) -> f32 {
+        self.uct.win_rate(self.uct.total_value(), self.uct.visits())
+    }
+
+    #[inline]
+    pub fn uct(&self, total_visits: u32) -> f32 {
+        self.uct.uct(total_visits)
+    }
+
+    /// Returns the importance weighted sum of all rewards of this move, as
+    /// used by EXP3.
+    pub fn estimate(&self) -> f32 {
+        f32::from_bits(self.estimate.load(Ordering::Relaxed))
+    }
+
+    /// Accumulate the given `value` of this move, which was chosen with the
+    /// given `probability`.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the reward of the player that played this move
+    /// * `probability` - the probability that this move was chosen
+    ///
+    pub(super) fn update(&self, value: f32, probability: f32) {
+        self.uct.update(&uct::Update::new(value));
+        self.estimate.fetch_update(Ordering::AcqRel, Ordering::Acquire, |prev_value| {
+            Some(f32::to_bits(f32::from_bits(prev_value) + value / probability))
+        }).unwrap();
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{maxn, mcts::Mcts, probe_status::ProbeStatus, process::{PerChild, Process, SelectResult}, search_rng::with_search_rng};
+use rand::{seq::SliceRandom, Rng};
+use smallvec::SmallVec;
+use std::marker::PhantomData;
+use super::{JointPerChild, MoveStats, SimultaneousGame, SimultaneousState};
+
+/// How every player independently chooses their part of the joint move.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Policy {
+    /// Decoupled UCT, where every player picks the move with the highest
+    /// upper confidence bound of their marginal statistics.
+    Duct,
+
+    /// EXP3, where every player samples a move from a distribution that
+    /// favours moves with a high importance weighted reward, mixed with a
+    /// uniform distribution of weight `gamma`.
+    Exp3 { gamma: f32 }
+}
+
+impl Policy {
+    /// Returns the probability that each of the given moves is chosen, or
+    /// `None` if the choice is deterministic.
+    ///
+    /// # Arguments
+    ///
+    /// * `stats` - the marginal statistics of every legal move
+    ///
+    pub fn probabilities(&self, stats: &[MoveStats]) -> Option<Vec<f32>> {
+        match *self {
+            Self::Duct => None,
+            Self::Exp3 { gamma } => {
+                let k = stats.len() as f32;
+                let eta = gamma / k;
+                let max_estimate = stats.iter().map(|stats| stats.estimate()).fold(f32::NEG_INFINITY, f32::max);
+                let weights = stats.iter().map(|stats| (eta * (stats.estimate() - max_estimate)).exp()).collect::<Vec<_>>();
+                let total_weight = weights.iter().sum::<f32>();
+
+                Some(weights.into_iter().map(|weight| (1.0 - gamma) * weight / total_weight + gamma / k).collect())
+            }
+        }
+    }
+
+    /// Returns the index of the chosen move, and the probability that it was
+    /// chosen.
+    fn choose(&self, stats: &[MoveStats], total_visits: u32, rng: &mut impl Rng) -> (usize, f32) {
+        match self.probabilities(stats) {
+            Some(probabilities) => {
+                let indices = (0..stats.len()).collect::<Vec<_>>();
+                let i = *indices.choose_weighted(rng, |&i| probabilities[i]).unwrap();
+
+                (i, probabilities[i])
+            },
+            None => {
+                let total_visits = total_visits.max(1);
+                let i = (0..stats.len())
+                    .max_by(|&a, &b| stats[a].uct(total_visits).total_cmp(&stats[b].uct(total_visits)))
+                    .unwrap();
+
+                (i, 1.0)
+            }
+        }
+    }
+}
+
+/// A monte-carlo process derived from the rules of a `SimultaneousGame`,
+/// where every node keeps separate statistics for the moves of every player,
+/// and the children are keyed by the joint move of all players.
+pub struct SimultaneousProcess<G: SimultaneousGame> {
+    policy: Policy,
+    game: PhantomData<fn() -> G>
+}
+
+impl<G: SimultaneousGame> Default for SimultaneousProcess<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G: SimultaneousGame> SimultaneousProcess<G> {
+    /// Returns a process that uses decoupled UCT to choose moves.
+    pub fn new() -> Self {
+        Self { policy: Policy::Duct, game: PhantomData }
+    }
+
+    /// Returns this process where every player uses the given `policy` to
+    /// choose their move.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - how to choose moves
+    ///
+    pub fn with_policy(self, policy: Policy) -> Self {
+        Self { policy, ..self }
+    }
+
+    /// Returns the reward of every player at the end of a game played from
+    /// `game` where every player picks uniformly random moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - the position to start the random game from
+    /// * `rng` - the source of randomness
+    ///
+    pub fn rollout(&self, game: &G, rng: &mut impl Rng) -> maxn::Update {
+        let mut game = game.clone();
+
+        while !game.is_terminal() {
+            let joint_move = (0..game.num_players())
+                .map(|player| game.legal_moves(player).choose(rng).copied())
+                .collect::<Option<SmallVec<[G::Move; 4]>>>();
+
+            match joint_move {
+                Some(joint_move) => game.play(&joint_move),
+                None => break
+            }
+        }
+
+        maxn::Update::new((0..game.num_players()).map(|player| game.reward(player)))
+    }
+}
+
+impl<G: SimultaneousGame> Process for SimultaneousProcess<G> {
+    type State = SimultaneousState<G>;
+    type PerChild = JointPerChild;
+    type Update = maxn::Update;
+
+    fn best<'a>(&self, _: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> Option<<Self::PerChild as PerChild>::Key> where Self::PerChild: 'a {
+        edges.max_by_key(|edge| edge.visits()).map(|edge| edge.key())
+    }
+
+    fn select<'a>(&self, state: &Self::State, mut edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        if !state.has_moves() {
+            return SelectResult::None;
+        }
+
+        let (indices, probabilities): (SmallVec<[usize; 4]>, SmallVec<[f32; 4]>) = with_search_rng(|mut rng| {
+            (0..state.game().num_players())
+                .map(|player| self.policy.choose(state.stats(player), state.visits(), &mut rng))
+                .unzip()
+        });
+        let key = state.joint_key(&indices);
+
+        if let Some(edge) = edges.find(|edge| edge.key() == key) {
+            edge.set_probabilities(&probabilities);
+
+            SelectResult::Existing(key)
+        } else {
+            SelectResult::Add(JointPerChild::new(key).with_probabilities(&probabilities))
+        }
+    }
+
+    /// The reward of every move is weighted by the probability with which it
+    /// was chosen when the joint move was selected, rather than the current
+    /// probability, as is required by EXP3.
+    fn update(&self, state: &Self::State, per_child: &Self::PerChild, update: &Self::Update, _: bool) {
+        state.update();
+        per_child.update();
+
+        for (player, i) in state.joint_indices(per_child.key()).into_iter().enumerate() {
+            state.stats(player)[i].update(update.value(player), per_child.probability(player));
+        }
+    }
+}
+
+/// Probe the given `search_tree` once, evaluate the resulting position with a
+/// random game, and update the search tree with the result. Returns the
+/// status of the probe, which is `ProbeStatus::Empty` if the root is a
+/// terminal position.
+///
+/// # Arguments
+///
+/// * `search_tree` - the search tree to probe
+/// * `rng` - the source of randomness for the random game
+///
+pub fn search_once<G: SimultaneousGame>(search_tree: &Mcts<SimultaneousProcess<G>>, rng: &mut impl Rng) -> ProbeStatus {
+    let (trace, status) = search_tree.probe();
+
+    if status == ProbeStatus::Cancelled {
+        search_tree.cancel(trace, false);
+    } else if let Some(last_step) = trace.steps().last() {
+        let game = last_step.map(|state, per_child| {
+            let mut game = state.game().clone();
+            game.play(&state.joint_move(per_child.key()));
+            game
+        });
+        let update = search_tree.process().rollout(&game, rng);
+        let new_state = trace.is_pending().then(|| SimultaneousState::new(game));
+
+        search_tree.update(trace, new_state, update);
+    }
+
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exp3_is_uniform_without_rewards() {
+        let stats = [MoveStats::new(), MoveStats::new()];
+
+        assert_eq!(Policy::Exp3 { gamma: 0.1 }.probabilities(&stats), Some(vec! [0.5, 0.5]));
+        assert_eq!(Policy::Duct.probabilities(&stats), None);
+    }
+
+    #[test]
+    fn exp3_favours_higher_estimates() {
+        let stats = [MoveStats::new(), MoveStats::new()];
+        stats[1].update(1.0, 0.5);
+
+        let probabilities = Policy::Exp3 { gamma: 0.1 }.probabilities(&stats).unwrap();
+        assert!(probabilities[1] > probabilities[0]);
+        assert!((probabilities.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+        assert!(probabilities[0] >= 0.05);
+    }
+
+    #[test]
+    fn exp3_choose_returns_probability_of_choice() {
+        let stats = [MoveStats::new(), MoveStats::new(), MoveStats::new()];
+        stats[2].update(1.0, 0.5);
+
+        let policy = Policy::Exp3 { gamma: 0.1 };
+        let probabilities = policy.probabilities(&stats).unwrap();
+        let (i, probability) = policy.choose(&stats, 1, &mut rand::thread_rng());
+
+        assert_eq!(probability, probabilities[i]);
+        assert_eq!(Policy::Duct.choose(&stats, 1, &mut rand::thread_rng()).1, 1.0);
+    }
+
+    #[test]
+    fn selected_probabilities_are_kept() {
+        let per_child = JointPerChild::new(0).with_probabilities(&[0.25, 0.5]);
+        assert_eq!((per_child.probability(0), per_child.probability(1)), (0.25, 0.5));
+
+        per_child.set_probabilities(&[0.75, 0.125]);
+        assert_eq!((per_child.probability(0), per_child.probability(1)), (0.75, 0.125));
+        assert_eq!(JointPerChild::new(0).probability(0), 1.0);
+    }
+}
@@ -0,0 +1,233 @@
+use crate::{process, uct};
+use smallvec::SmallVec;
+use super::{MoveStats, SimultaneousGame};
+
+/// A position in a `SimultaneousProcess`, which keeps the marginal statistics
+/// of every legal move of every player.
+pub struct SimultaneousState<G: SimultaneousGame> {
+    game: G,
+    moves: Vec<Vec<G::Move>>,
+    stats: Vec<Vec<MoveStats>>,
+    uct: uct::State
+}
+
+impl<G: SimultaneousGame> process::State for SimultaneousState<G> {
+    fn hash(&self) -> Option<u64> {
+        self.game.hash()
+    }
+
+    fn hash128(&self) -> Option<u128> {
+        self.game.hash128()
+    }
+
+    fn same_as(&self, other: &Self) -> bool {
+        self.game.same_as(&other.game)
+    }
+}
+
+impl<G: SimultaneousGame> SimultaneousState<G> {
+    /// Returns the position of the given `game`.
+    ///
+    /// # Arguments
+    ///
+    /// * `game` - the game in this position
+    ///
+    /// # Panics
+    ///
+    /// If the number of joint moves, the product of the number of legal
+    /// moves of every player, does not fit in a `u64`.
+    ///
+    pub fn new(game: G) -> Self {
+        let moves = if game.is_terminal() {
+            vec! []
+        } else {
+            (0..game.num_players()).map(|player| game.legal_moves(player)).collect::<Vec<_>>()
+        };
+        let num_joint_moves = moves.iter().try_fold(1u64, |n, moves| n.checked_mul(moves.len() as u64));
+        assert!(num_joint_moves.is_some(), "the number of joint moves does not fit in a u64");
+        let stats = moves.iter().map(|moves| moves.iter().map(|_| MoveStats::new()).collect()).collect();
+
+        Self { game, moves, stats, uct: uct::State::new() }
+    }
+
+    /// Returns the game in this position.
+    pub fn game(&self) -> &G {
+        &self.game
+    }
+
+    /// Returns the number of times this position has been visited.
+    pub fn visits(&self) -> u32 {
+        self.uct.visits()
+    }
+
+    /// Returns if every player has at least one legal move in this position.
+    pub fn has_moves(&self) -> bool {
+        !self.moves.is_empty() && self.moves.iter().all(|moves| !moves.is_empty())
+    }
+
+    /// Returns the legal moves of the given `player`.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the index of the player
+    ///
+    pub fn moves(&self, player: usize) -> &[G::Move] {
+        &self.moves[player]
+    }
+
+    /// Returns the marginal statistics of every legal move of the given
+    /// `player`, in the same order as `moves`.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the index of the player
+    ///
+    pub fn stats(&self, player: usize) -> &[MoveStats] {
+        &self.stats[player]
+    }
+
+    /// Returns the most visited move of the given `player`.
+    ///
+    /// # Arguments
+    ///
+    /// * `player` - the index of the player
+    ///
+    pub fn best_move(&self, player: usize) -> Option<G::Move> {
+        self.stats(player).iter()
+            .zip(self.moves(player).iter())
+            .max_by_key(|(stats, _)| stats.visits())
+            .map(|(_, &mv)| mv)
+    }
+
+    /// Returns the key of the joint move where every player plays the move at
+    /// the given index of their legal moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `indices` - the index of the move of every player
+    ///
+    pub fn joint_key(&self, indices: &[usize]) -> u64 {
+        indices.iter().zip(self.moves.iter()).rev().fold(0, |key, (&i, moves)| {
+            key * moves.len() as u64 + i as u64
+        })
+    }
+
+    /// Returns the index of the move of every player in the joint move with
+    /// the given `key`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key of the joint move
+    ///
+    pub fn joint_indices(&self, mut key: u64) -> SmallVec<[usize; 4]> {
+        self.moves.iter().map(|moves| {
+            let i = key % moves.len() as u64;
+            key /= moves.len() as u64;
+            i as usize
+        }).collect()
+    }
+
+    /// Returns the move of every player in the joint move with the given
+    /// `key`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the key of the joint move
+    ///
+    pub fn joint_move(&self, key: u64) -> SmallVec<[G::Move; 4]> {
+        self.joint_indices(key).into_iter()
+            .enumerate()
+            .map(|(player, i)| self.moves[player][i])
+            .collect()
+    }
+
+    pub(super) fn update(&self) {
+        self.uct.update();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone)]
+    struct Sizes;
+
+    impl SimultaneousGame for Sizes {
+        type Move = u8;
+
+        fn num_players(&self) -> usize {
+            3
+        }
+
+        fn legal_moves(&self, player: usize) -> Vec<u8> {
+            (0..(player as u8 + 2)).map(|i| 10 * (player as u8) + i).collect()
+        }
+
+        fn play(&mut self, _: &[u8]) {
+            // pass
+        }
+
+        fn is_terminal(&self) -> bool {
+            false
+        }
+
+        fn reward(&self, _: usize) -> f32 {
+            0.0
+        }
+    }
+
+    #[derive(Clone)]
+    struct Wide;
+
+    impl SimultaneousGame for Wide {
+        type Move = u8;
+
+        fn num_players(&self) -> usize {
+            65
+        }
+
+        fn legal_moves(&self, _: usize) -> Vec<u8> {
+            vec! [0, 1]
+        }
+
+        fn play(&mut self, _: &[u8]) {
+            // pass
+        }
+
+        fn is_terminal(&self) -> bool {
+            false
+        }
+
+        fn reward(&self, _: usize) -> f32 {
+            0.0
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in a u64")]
+    fn new_panics_if_joint_moves_overflow() {
+        SimultaneousState::new(Wide);
+    }
+
+    #[test]
+    fn joint_key_round_trip() {
+        let state = SimultaneousState::new(Sizes);
+        let mut keys = vec! [];
+
+        for a in 0..2 {
+            for b in 0..3 {
+                for c in 0..4 {
+                    let key = state.joint_key(&[a, b, c]);
+
+                    assert_eq!(state.joint_indices(key).as_slice(), &[a, b, c]);
+                    keys.push(key);
+                }
+            }
+        }
+
+        keys.sort();
+        assert_eq!(keys, (0..24).collect::<Vec<_>>());
+        assert_eq!(state.joint_move(state.joint_key(&[1, 2, 3])).as_slice(), &[1, 12, 23]);
+    }
+}
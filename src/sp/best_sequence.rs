@@ -0,0 +1,59 @@
+use std::sync::Mutex;
+
+/// The best complete sequence of moves found so far in a single-player
+/// search, including any moves played outside of the search tree.
+pub struct BestSequence<K> {
+    best: Mutex<Option<(f32, Vec<K>)>>
+}
+
+impl<K: Clone> Default for BestSequence<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Clone> BestSequence<K> {
+    pub fn new() -> Self {
+        Self { best: Mutex::new(None) }
+    }
+
+    /// Record the given `sequence` if its `reward` is better than the best
+    /// reward seen so far. Returns true iff the sequence was recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `reward` - the reward at the end of the sequence
+    /// * `sequence` - the moves played from the root
+    ///
+    pub fn record(&self, reward: f32, sequence: impl IntoIterator<Item=K>) -> bool {
+        let mut best = self.best.lock().unwrap();
+
+        if best.as_ref().map(|(best_reward, _)| reward > *best_reward).unwrap_or(true) {
+            *best = Some((reward, sequence.into_iter().collect()));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the best reward seen so far, and the sequence of moves that
+    /// led to it.
+    pub fn get(&self) -> Option<(f32, Vec<K>)> {
+        self.best.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_keeps_the_best() {
+        let best = BestSequence::new();
+
+        assert!(best.record(1.0, [1, 2]));
+        assert!(best.record(3.0, [2, 3, 4]));
+        assert!(!best.record(2.0, [3]));
+        assert_eq!(best.get(), Some((3.0, vec! [2, 3, 4])));
+    }
+}
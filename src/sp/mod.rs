@@ -0,0 +1,7 @@
+mod best_sequence;
+mod per_child;
+mod update;
+
+pub use self::best_sequence::*;
+pub use self::per_child::*;
+pub use self::update::*;
@@ -0,0 +1,203 @@
+use crate::packed::{pack, unpack};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use super::update::Update;
+
+/// The statistics of an edge in a single-player search, which tracks the mean,
+/// the maximum, and the sum of squares of all rewards seen through it.
+///
+/// The total reward and the number of visits are packed into a single atomic,
+/// so the mean is always consistent. The sum of squares and the maximum are
+/// each updated atomically on their own, so a concurrent reader may see them
+/// include rewards that the mean does not include yet, or the other way
+/// around. This only skews the deviation term of `score` by the rewards that
+/// are being added at that moment.
+pub struct PerChild {
+    atomic_per_child: AtomicU64,
+    sum_of_squares: AtomicU64,
+    max: AtomicU32
+}
+
+impl Clone for PerChild {
+    fn clone(&self) -> Self {
+        Self {
+            atomic_per_child: AtomicU64::new(self.atomic_per_child.load(Ordering::Relaxed)),
+            sum_of_squares: AtomicU64::new(self.sum_of_squares.load(Ordering::Relaxed)),
+            max: AtomicU32::new(self.max.load(Ordering::Relaxed))
+        }
+    }
+}
+
+impl Default for PerChild {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PerChild {
+    pub fn new() -> Self {
+        Self {
+            atomic_per_child: AtomicU64::new(pack(0.0, 0)),
+            sum_of_squares: AtomicU64::new(f64::to_bits(0.0)),
+            max: AtomicU32::new(f32::to_bits(f32::NEG_INFINITY))
+        }
+    }
+
+    pub fn update(&self, up: &Update) {
+        let reward = up.reward();
+
+        self.atomic_per_child.fetch_update(Ordering::AcqRel, Ordering::Acquire, |prev_value| {
+            let (value, total_visits) = unpack(prev_value);
+
+            Some(pack(value + reward, total_visits + 1))
+        }).unwrap();
+        self.sum_of_squares.fetch_update(Ordering::AcqRel, Ordering::Acquire, |prev_value| {
+            Some(f64::to_bits(f64::from_bits(prev_value) + (reward as f64) * (reward as f64)))
+        }).unwrap();
+        let _ = self.max.fetch_update(Ordering::AcqRel, Ordering::Acquire, |prev_value| {
+            if reward > f32::from_bits(prev_value) {
+                Some(f32::to_bits(reward))
+            } else {
+                None
+            }
+        });
+    }
+
+    #[inline]
+    pub fn visits(&self) -> u32 {
+        unpack(self.atomic_per_child.load(Ordering::Relaxed)).1
+    }
+
+    #[inline]
+    pub fn total_value(&self) -> f32 {
+        unpack(self.atomic_per_child.load(Ordering::Relaxed)).0
+    }
+
+    /// Returns the average reward seen through this edge.
+    #[inline]
+    pub fn mean(&self) -> f32 {
+        let (value, visits) = unpack(self.atomic_per_child.load(Ordering::Relaxed));
+
+        if visits > 0 {
+            value / visits as f32
+        } else {
+            0.0f32
+        }
+    }
+
+    /// Returns the best reward seen through this edge, or `None` if it has
+    /// not been visited.
+    #[inline]
+    pub fn max(&self) -> Option<f32> {
+        let max = f32::from_bits(self.max.load(Ordering::Relaxed));
+
+        if max == f32::NEG_INFINITY {
+            None
+        } else {
+            Some(max)
+        }
+    }
+
+    /// Returns the sum of the squares of all rewards seen through this edge.
+    #[inline]
+    pub fn sum_of_squares(&self) -> f64 {
+        f64::from_bits(self.sum_of_squares.load(Ordering::Relaxed))
+    }
+
+    /// Returns the SP-MCTS selection score of this edge, which adds a term for
+    /// the possible deviation of the rewards to the usual UCT formula.
+    ///
+    /// # Arguments
+    ///
+    /// * `total_visits` - the number of visits to the parent
+    /// * `c` - the exploration constant
+    /// * `d` - the constant added to the variance, which makes rarely
+    ///   visited edges with a small deviation still uncertain
+    ///
+    #[inline]
+    pub fn score(&self, total_visits: u32, c: f32, d: f32) -> f32 {
+        let (value, visits) = unpack(self.atomic_per_child.load(Ordering::Relaxed));
+
+        if visits == 0 {
+            return f32::INFINITY;
+        }
+
+        let n = visits as f64;
+        let mean = value as f64 / n;
+        let exploration = c as f64 * ((total_visits as f64).ln() / n).sqrt();
+        let deviation = ((self.sum_of_squares() - n * mean * mean + d as f64) / n).max(0.0).sqrt();
+
+        (mean + exploration + deviation) as f32
+    }
+}
+
+/// Returns the key of the edge in `edges` with the highest maximum reward,
+/// which when used as `Process::best` makes `Mcts::path` follow the best
+/// sequence seen in the search tree rather than the most visited one.
+///
+/// # Arguments
+///
+/// * `edges` - the edges to choose from
+/// * `per_child` - returns the statistics and key of an edge
+///
+pub fn best<'a, E: 'a, K>(edges: impl Iterator<Item=&'a E>, per_child: impl Fn(&E) -> (&PerChild, K)) -> Option<K> {
+    let mut best: Option<(f32, K)> = None;
+
+    for edge in edges {
+        let (per_child, key) = per_child(edge);
+
+        if let Some(max) = per_child.max() {
+            if best.as_ref().map(|(best_max, _)| max > *best_max).unwrap_or(true) {
+                best = Some((max, key));
+            }
+        }
+    }
+
+    best.map(|(_, key)| key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update_tracks_mean_max_and_squares() {
+        let per_child = PerChild::new();
+        assert_eq!(per_child.max(), None);
+
+        per_child.update(&Update::new(1.0));
+        per_child.update(&Update::new(3.0));
+
+        assert_eq!(per_child.visits(), 2);
+        assert_eq!(per_child.mean(), 2.0);
+        assert_eq!(per_child.max(), Some(3.0));
+        assert_eq!(per_child.sum_of_squares(), 10.0);
+    }
+
+    #[test]
+    fn score_includes_deviation() {
+        let stable = PerChild::new();
+        let unstable = PerChild::new();
+
+        for reward in [2.0, 2.0, 2.0, 2.0] {
+            stable.update(&Update::new(reward));
+        }
+        for reward in [0.0, 4.0, 0.0, 4.0] {
+            unstable.update(&Update::new(reward));
+        }
+
+        assert_eq!(stable.score(8, 0.0, 0.0), 2.0);
+        assert_eq!(unstable.score(8, 0.0, 0.0), 4.0);
+        assert!(PerChild::new().score(8, 0.5, 1.0).is_infinite());
+    }
+
+    #[test]
+    fn best_picks_highest_max() {
+        let edges = [(0, PerChild::new()), (1, PerChild::new()), (2, PerChild::new())];
+        edges[0].1.update(&Update::new(1.0));
+        edges[0].1.update(&Update::new(1.0));
+        edges[1].1.update(&Update::new(0.0));
+        edges[1].1.update(&Update::new(5.0));
+
+        assert_eq!(best(edges.iter(), |(key, edge)| (edge, *key)), Some(1));
+    }
+}
@@ -0,0 +1,20 @@
+#[derive(Clone, Debug, PartialEq)]
+pub struct Update {
+    reward: f32
+}
+
+impl Update {
+    pub fn new(reward: f32) -> Self {
+        Self { reward }
+    }
+
+    pub fn reward(&self) -> f32 {
+        self.reward
+    }
+}
+
+impl From<f32> for Update {
+    fn from(reward: f32) -> Self {
+        Self::new(reward)
+    }
+}
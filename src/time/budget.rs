@@ -0,0 +1,143 @@
+use crate::{mcts::Mcts, process::{PerChild, Process}};
+use std::time::{Duration, Instant};
+
+/// The amount of time a search may spend on a single move. The budget starts
+/// out at its `allotted` time, and is stretched towards its `maximum` each
+/// time the best move changes between two observations of the search tree.
+#[derive(Clone, Debug)]
+pub struct Budget<K> {
+    started: Instant,
+    base: Duration,
+    allotted: Duration,
+    maximum: Duration,
+    best: Option<K>
+}
+
+impl<K> Budget<K> {
+    /// Returns a budget that started at `started`, and that allots `allotted`
+    /// time but may be stretched to at most `maximum`.
+    ///
+    /// # Arguments
+    ///
+    /// * `started` - when the search was started
+    /// * `allotted` - the time to spend if the best move is stable
+    /// * `maximum` - the most time to spend if the best move is unstable
+    ///
+    pub fn new(started: Instant, allotted: Duration, maximum: Duration) -> Self {
+        let maximum = maximum.max(allotted);
+
+        Self { started, base: allotted, allotted, maximum, best: None }
+    }
+
+    /// Returns the time currently allotted to this search.
+    pub fn allotted(&self) -> Duration {
+        self.allotted
+    }
+
+    /// Returns the most time this search may be allotted.
+    pub fn maximum(&self) -> Duration {
+        self.maximum
+    }
+
+    /// Returns the time elapsed since this search was started.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Returns the time remaining of the allotted time.
+    pub fn remaining(&self) -> Duration {
+        self.allotted.saturating_sub(self.elapsed())
+    }
+
+    /// Returns the instant at which the search should stop, or `None` if
+    /// there is no time limit.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.started.checked_add(self.allotted)
+    }
+
+    /// Returns if the allotted time has run out.
+    pub fn is_expired(&self) -> bool {
+        self.elapsed() >= self.allotted
+    }
+}
+
+impl<K: Copy + PartialEq> Budget<K> {
+    /// Record the current `best` move, and stretch the allotted time if it
+    /// differs from the previously recorded best move. Returns true iff the
+    /// allotted time was stretched.
+    ///
+    /// # Arguments
+    ///
+    /// * `best` - the current best move
+    ///
+    pub fn observe_best(&mut self, best: Option<K>) -> bool {
+        let is_unstable = self.best.is_some() && self.best != best;
+
+        if is_unstable {
+            self.allotted = self.allotted.saturating_add(self.base / 2).min(self.maximum);
+        }
+
+        self.best = best;
+        is_unstable
+    }
+
+    /// Record the first step of the current principal variation of the given
+    /// `search_tree`, and stretch the allotted time if it has changed since
+    /// the previous observation. Returns true iff the allotted time was
+    /// stretched.
+    ///
+    /// # Arguments
+    ///
+    /// * `search_tree` - the search tree to observe
+    ///
+    pub fn observe<P>(&mut self, search_tree: &Mcts<P>) -> bool
+        where P: Process, P::PerChild: PerChild<Key=K>
+    {
+        let best = search_tree.path().next().map(|step| step.key());
+
+        self.observe_best(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn observe_best_stretches_when_unstable() {
+        let mut budget = Budget::new(Instant::now(), Duration::from_secs(2), Duration::from_secs(8));
+
+        assert!(!budget.observe_best(Some(1)));
+        assert!(!budget.observe_best(Some(1)));
+        assert_eq!(budget.allotted(), Duration::from_secs(2));
+        assert!(budget.observe_best(Some(2)));
+        assert_eq!(budget.allotted(), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn observe_best_never_exceeds_maximum() {
+        let mut budget = Budget::new(Instant::now(), Duration::from_secs(2), Duration::from_secs(4));
+
+        for i in 0..10 {
+            budget.observe_best(Some(i));
+        }
+
+        assert_eq!(budget.allotted(), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn is_expired_when_no_time_is_allotted() {
+        let budget = Budget::<()>::new(Instant::now(), Duration::ZERO, Duration::ZERO);
+
+        assert!(budget.is_expired());
+        assert_eq!(budget.remaining(), Duration::ZERO);
+    }
+
+    #[test]
+    fn deadline_is_none_when_infinite() {
+        let budget = Budget::<()>::new(Instant::now(), Duration::MAX, Duration::MAX);
+
+        assert!(budget.deadline().is_none());
+        assert!(!budget.is_expired());
+    }
+}
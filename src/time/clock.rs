@@ -0,0 +1,217 @@
+use std::time::{Duration, Instant};
+use super::budget::Budget;
+
+/// The number of moves we assume remain in the game when the time control
+/// does not tell us.
+const EXPECTED_MOVES_TO_GO: u32 = 30;
+
+/// The factor by which the allotted time may at most be stretched when the
+/// best move is unstable, i.e. the maximum budget is this many times the
+/// allotted time.
+const MAX_STRETCH: u32 = 4;
+
+/// The time controls of a game, together with the time that remains on the
+/// clock for the player to move.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clock {
+    main_time: Duration,
+    increment: Duration,
+    byoyomi_time: Duration,
+    byoyomi_periods: u32,
+    moves_to_go: Option<u32>,
+    margin: Duration
+}
+
+impl Clock {
+    /// Returns a sudden death clock with the given `main_time` remaining.
+    ///
+    /// # Arguments
+    ///
+    /// * `main_time` - the main time remaining
+    ///
+    pub fn new(main_time: Duration) -> Self {
+        Self {
+            main_time,
+            increment: Duration::ZERO,
+            byoyomi_time: Duration::ZERO,
+            byoyomi_periods: 0,
+            moves_to_go: None,
+            margin: Duration::from_millis(50)
+        }
+    }
+
+    /// Returns this clock with an `increment` added after every move.
+    ///
+    /// # Arguments
+    ///
+    /// * `increment` - the time added after every move
+    ///
+    pub fn with_increment(self, increment: Duration) -> Self {
+        Self { increment, ..self }
+    }
+
+    /// Returns this clock with `periods` byoyomi periods of `time` each,
+    /// which are used once the main time has run out.
+    ///
+    /// # Arguments
+    ///
+    /// * `time` - the length of each byoyomi period
+    /// * `periods` - the number of byoyomi periods remaining
+    ///
+    pub fn with_byoyomi(self, time: Duration, periods: u32) -> Self {
+        Self { byoyomi_time: time, byoyomi_periods: periods, ..self }
+    }
+
+    /// Returns this clock where the remaining time has to last for the next
+    /// `moves_to_go` moves.
+    ///
+    /// # Arguments
+    ///
+    /// * `moves_to_go` - the number of moves until the next time control
+    ///
+    pub fn with_moves_to_go(self, moves_to_go: u32) -> Self {
+        Self { moves_to_go: Some(moves_to_go), ..self }
+    }
+
+    /// Returns this clock with a safety `margin` subtracted from every
+    /// budget, to account for communication and scheduling overhead.
+    ///
+    /// # Arguments
+    ///
+    /// * `margin` - the time to keep in reserve
+    ///
+    pub fn with_margin(self, margin: Duration) -> Self {
+        Self { margin, ..self }
+    }
+
+    /// Set the time that remains on this clock, typically as reported by the
+    /// game server after every move.
+    ///
+    /// # Arguments
+    ///
+    /// * `main_time` - the main time remaining
+    /// * `byoyomi_periods` - the number of byoyomi periods remaining
+    ///
+    pub fn set_remaining(&mut self, main_time: Duration, byoyomi_periods: u32) {
+        self.main_time = main_time;
+        self.byoyomi_periods = byoyomi_periods;
+    }
+
+    /// Returns the main time remaining on this clock.
+    pub fn main_time(&self) -> Duration {
+        self.main_time
+    }
+
+    /// Returns the number of byoyomi periods remaining on this clock.
+    pub fn byoyomi_periods(&self) -> u32 {
+        self.byoyomi_periods
+    }
+
+    /// Returns if this clock has no time limit at all.
+    pub fn is_infinite(&self) -> bool {
+        self.main_time == Duration::MAX
+    }
+
+    fn byoyomi(&self) -> Duration {
+        if self.byoyomi_periods > 0 {
+            self.byoyomi_time.saturating_sub(self.margin)
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    /// Returns the search budget for the next move, starting now.
+    pub fn budget<K>(&self) -> Budget<K> {
+        self.budget_at(Instant::now())
+    }
+
+    /// Returns the search budget for the next move, starting at `started`.
+    ///
+    /// # Arguments
+    ///
+    /// * `started` - when the search for the next move was started
+    ///
+    pub fn budget_at<K>(&self, started: Instant) -> Budget<K> {
+        if self.is_infinite() {
+            return Budget::new(started, Duration::MAX, Duration::MAX);
+        }
+
+        let available = self.main_time.saturating_sub(self.margin);
+        let byoyomi = self.byoyomi();
+
+        if available.is_zero() {
+            Budget::new(started, byoyomi, byoyomi)
+        } else {
+            let moves_to_go = self.moves_to_go.unwrap_or(EXPECTED_MOVES_TO_GO).max(1);
+            let allotted = (available / moves_to_go).saturating_add(self.increment).min(available).max(byoyomi);
+            let maximum = allotted.checked_mul(MAX_STRETCH).unwrap_or(Duration::MAX).min(available.saturating_add(byoyomi));
+
+            Budget::new(started, allotted, maximum)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_splits_main_time() {
+        let started = Instant::now();
+        let clock = Clock::new(Duration::from_secs(60)).with_margin(Duration::ZERO);
+        let budget = clock.budget_at::<()>(started);
+
+        assert_eq!(budget.allotted(), Duration::from_secs(2));
+        assert_eq!(budget.maximum(), Duration::from_secs(8));
+    }
+
+    #[test]
+    fn budget_adds_increment() {
+        let started = Instant::now();
+        let clock = Clock::new(Duration::from_secs(60))
+            .with_increment(Duration::from_secs(1))
+            .with_moves_to_go(20)
+            .with_margin(Duration::ZERO);
+
+        assert_eq!(clock.budget_at::<()>(started).allotted(), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn budget_uses_byoyomi_when_main_time_is_out() {
+        let started = Instant::now();
+        let mut clock = Clock::new(Duration::from_secs(60))
+            .with_byoyomi(Duration::from_secs(10), 3)
+            .with_margin(Duration::from_secs(1));
+        clock.set_remaining(Duration::ZERO, 2);
+        let budget = clock.budget_at::<()>(started);
+
+        assert_eq!(budget.allotted(), Duration::from_secs(9));
+        assert_eq!(budget.maximum(), Duration::from_secs(9));
+    }
+
+    #[test]
+    fn budget_never_exceeds_remaining_time() {
+        let started = Instant::now();
+        let clock = Clock::new(Duration::from_secs(3))
+            .with_increment(Duration::from_secs(10))
+            .with_margin(Duration::ZERO);
+        let budget = clock.budget_at::<()>(started);
+
+        assert_eq!(budget.allotted(), Duration::from_secs(3));
+        assert_eq!(budget.maximum(), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn budget_saturates_for_huge_main_time() {
+        let started = Instant::now();
+        let clock = Clock::new(Duration::MAX - Duration::from_secs(1))
+            .with_increment(Duration::from_secs(10))
+            .with_byoyomi(Duration::from_secs(10), 1)
+            .with_moves_to_go(1)
+            .with_margin(Duration::ZERO);
+        let budget = clock.budget_at::<()>(started);
+
+        assert_eq!(budget.allotted(), Duration::MAX - Duration::from_secs(1));
+        assert_eq!(budget.maximum(), Duration::MAX);
+    }
+}
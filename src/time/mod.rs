@@ -0,0 +1,5 @@
+mod budget;
+mod clock;
+
+pub use self::budget::*;
+pub use self::clock::*;
@@ -1,19 +1,93 @@
-use crate::{process::{PerChild, Process}, safe_nonnull::SafeNonNull, step::Step};
-use crossbeam_epoch::Guard;
-use std::rc::Rc;
+use crate::{edge::Edge, joined::ReleasePending, process::{PerChild, Process}, safe_nonnull::SafeNonNull, step::Step};
 
+/// The edges taken by a single probe through a search tree, from the root
+/// towards the leaves. A trace borrows the search tree, but does not pin the
+/// current thread, so it can be evaluated and used to update the search tree
+/// on a different thread than the one that probed it.
+///
+/// A trace whose last edge has not been expanded marks that edge as pending
+/// until the trace is updated, cancelled, or dropped.
 pub struct Trace<'a, P: Process, Node> {
     steps: Vec<Step<'a, P, Node>>,
+    pending: Option<SafeNonNull<Edge<P, Node>>>,
+    is_expanded: bool,
+    releaser: Option<(&'a P, &'a dyn ReleasePending<P, Node>)>
+}
+
+impl<'a, P: Process, Node> Drop for Trace<'a, P, Node> {
+    fn drop(&mut self) {
+        if let Some(edge) = self.pending.take() {
+            match self.releaser {
+                Some((process, releaser)) => releaser.release(process, edge),
+                None => edge.release()
+            }
+        }
+    }
 }
 
 impl<'a, P: Process, Node> Trace<'a, P, Node> {
-    /// Returns a new trace with the given `steps` and `status`.
+    /// Returns an empty trace, which has no pending edge.
     pub fn new() -> Self {
-        Self { steps: vec! [] }
+        Self { steps: vec! [], pending: None, is_expanded: false, releaser: None }
+    }
+
+    /// Returns this trace, where the pending edge is released through the
+    /// given `releaser` if this trace is dropped without being updated.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - the process to pass to the releaser
+    /// * `releaser` - releases the pending edge
+    ///
+    pub(super) fn with_releaser(mut self, process: &'a P, releaser: &'a dyn ReleasePending<P, Node>) -> Self {
+        self.releaser = Some((process, releaser));
+        self
+    }
+
+    pub(super) fn push(&mut self, process: &'a P, ptr: SafeNonNull<Node>, key: <P::PerChild as PerChild>::Key) {
+        self.steps.push(Step::new(process, ptr, key));
+    }
+
+    /// Add the final step of this trace, which ends at the unexpanded `edge`.
+    /// Returns false, without adding the step, if the edge has been retired.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` -
+    /// * `ptr` - the node the edge starts from
+    /// * `edge` - the edge to mark as pending
+    /// * `is_expanded` - whether the edge was added by this trace
+    ///
+    pub(super) fn push_pending(&mut self, process: &'a P, ptr: SafeNonNull<Node>, edge: SafeNonNull<Edge<P, Node>>, is_expanded: bool) -> bool {
+        if edge.acquire() {
+            self.steps.push(Step::new(process, ptr, edge.key()));
+            self.pending = Some(edge);
+            self.is_expanded = is_expanded;
+            true
+        } else {
+            false
+        }
     }
 
-    pub(super) fn push(&mut self, process: &'a P, pin: Rc<Guard>, ptr: SafeNonNull<Node>, key: <P::PerChild as PerChild>::Key) {
-        self.steps.push(Step::new(process, pin, ptr, key));
+    /// Returns the pending edge at the end of this trace, and whether it was
+    /// added by this trace, which are no longer released when this trace is
+    /// dropped.
+    pub(super) fn take_pending(&mut self) -> Option<(SafeNonNull<Edge<P, Node>>, bool)> {
+        self.pending.take().map(|edge| (edge, self.is_expanded))
+    }
+
+    /// Returns if the last step of this trace ends at an edge that had no
+    /// child when it was probed, so a state should be added for it when this
+    /// trace is updated.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Returns if the last step of this trace ends at an edge that was added
+    /// by this trace, as for `ProbeStatus::Expanded`, which is the only kind
+    /// of trace that a state should be added for when it is updated.
+    pub fn is_expanded(&self) -> bool {
+        self.pending.is_some() && self.is_expanded
     }
 
     /// Returns if there are no steps in this trace.
@@ -22,15 +96,14 @@ impl<'a, P: Process, Node> Trace<'a, P, Node> {
     }
 
     /// Returns the steps in this trace.
-    pub fn steps(&self) -> &[Step<P, Node>] {
+    pub fn steps(&self) -> &[Step<'a, P, Node>] {
         &self.steps
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::FakeProcess;
-    use crossbeam_epoch as epoch;
+    use crate::{node::Node, FakeProcess};
     use super::*;
 
     #[test]
@@ -42,9 +115,18 @@ mod tests {
     fn push_adds_one_step() {
         let process = FakeProcess::new(0, 0);
         let mut trace = Trace::<FakeProcess, ()>::new();
-        trace.push(&process, Rc::new(epoch::pin()), SafeNonNull::new(()), 0);
+        trace.push(&process, SafeNonNull::new(()), 0);
 
         assert!(!trace.is_empty());
         assert_eq!(trace.steps().len(), 1);
     }
+
+    #[test]
+    fn trace_is_send() {
+        fn assert_send<T: Send>() {
+            // pass
+        }
+
+        assert_send::<Trace<'static, FakeProcess, Node<FakeProcess>>>();
+    }
 }
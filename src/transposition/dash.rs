@@ -0,0 +1,42 @@
+use dashmap::DashMap;
+use super::{TableEntry, TranspositionTable};
+
+/// A transposition table that never forgets an entry, backed by a `DashMap`
+/// with the default hasher.
+pub struct DashTable {
+    entries: DashMap<u128, TableEntry>
+}
+
+impl Default for DashTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DashTable {
+    pub fn new() -> Self {
+        Self { entries: DashMap::with_capacity(32) }
+    }
+}
+
+impl TranspositionTable for DashTable {
+    fn get(&self, hash: u128) -> Option<TableEntry> {
+        self.entries.get(&hash).map(|entry| *entry.value())
+    }
+
+    fn insert(&self, hash: u128, entry: TableEntry, _: u32) {
+        self.entries.insert(hash, entry);
+    }
+
+    fn retain(&mut self, keep: &mut dyn FnMut(&TableEntry) -> bool) {
+        self.entries.retain(|_, entry| keep(entry));
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
@@ -0,0 +1,28 @@
+use super::{TableEntry, TranspositionTable};
+
+/// A transposition table that never stores anything, so no nodes are shared
+/// and the search tree is a tree.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DisabledTable;
+
+impl TranspositionTable for DisabledTable {
+    fn get(&self, _: u128) -> Option<TableEntry> {
+        None
+    }
+
+    fn insert(&self, _: u128, _: TableEntry, _: u32) {
+        // pass
+    }
+
+    fn retain(&mut self, _: &mut dyn FnMut(&TableEntry) -> bool) {
+        // pass
+    }
+
+    fn clear(&mut self) {
+        // pass
+    }
+
+    fn len(&self) -> usize {
+        0
+    }
+}
@@ -0,0 +1,175 @@
+use std::sync::{atomic::{AtomicUsize, Ordering}, Mutex};
+use super::{TableEntry, TranspositionTable};
+
+/// Which entry a `FixedTable` keeps when two hashes map to the same slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Replacement {
+    /// Always keep the newest entry.
+    Always,
+
+    /// Keep the entry closest to the root, since it usually represents more
+    /// search effort. Ties are won by the newest entry.
+    Depth,
+
+    /// Keep the entry whose node has been visited the most. Ties are won by
+    /// the newest entry.
+    Visits
+}
+
+struct Slot {
+    hash: u128,
+    entry: TableEntry,
+    depth: u32
+}
+
+/// A transposition table with a fixed number of slots, where every hash maps
+/// to a single slot and a collision between two hashes is resolved by a
+/// `Replacement` policy. This bounds the memory used by the table no matter
+/// how large the search tree grows.
+pub struct FixedTable {
+    slots: Vec<Mutex<Option<Slot>>>,
+    replacement: Replacement,
+    len: AtomicUsize
+}
+
+impl FixedTable {
+    /// Returns an empty table with at least the given number of slots, which
+    /// is rounded up to a power of two.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - the minimum number of slots
+    /// * `replacement` - which entry to keep when two hashes collide
+    ///
+    pub fn new(capacity: usize, replacement: Replacement) -> Self {
+        let capacity = capacity.max(1).next_power_of_two();
+        let slots = (0..capacity).map(|_| Mutex::new(None)).collect();
+
+        Self { slots, replacement, len: AtomicUsize::new(0) }
+    }
+
+    /// Returns the number of slots in this table.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn slot(&self, hash: u128) -> &Mutex<Option<Slot>> {
+        let folded = (hash >> 64) as u64 ^ hash as u64;
+
+        &self.slots[folded as usize & (self.slots.len() - 1)]
+    }
+
+    fn replaces(&self, existing: &Slot, depth: u32, entry: &TableEntry) -> bool {
+        match self.replacement {
+            Replacement::Always => true,
+            Replacement::Depth => depth <= existing.depth,
+            Replacement::Visits => entry.visits() >= existing.entry.visits()
+        }
+    }
+}
+
+impl TranspositionTable for FixedTable {
+    fn get(&self, hash: u128) -> Option<TableEntry> {
+        match &*self.slot(hash).lock().unwrap() {
+            Some(slot) if slot.hash == hash => Some(slot.entry),
+            _ => None
+        }
+    }
+
+    fn insert(&self, hash: u128, entry: TableEntry, depth: u32) {
+        let mut slot = self.slot(hash).lock().unwrap();
+
+        match &*slot {
+            None => {
+                self.len.fetch_add(1, Ordering::Relaxed);
+            },
+            Some(existing) if existing.hash == hash || self.replaces(existing, depth, &entry) => {
+                // pass
+            },
+            Some(_) => return
+        }
+
+        *slot = Some(Slot { hash, entry, depth });
+    }
+
+    fn retain(&mut self, keep: &mut dyn FnMut(&TableEntry) -> bool) {
+        for slot in &mut self.slots {
+            let slot = slot.get_mut().unwrap();
+
+            if slot.as_ref().map(|slot| !keep(&slot.entry)).unwrap_or(false) {
+                *slot = None;
+                *self.len.get_mut() -= 1;
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        for slot in &mut self.slots {
+            *slot.get_mut().unwrap() = None;
+        }
+
+        *self.len.get_mut() = 0;
+    }
+
+    fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{node::Node, safe_nonnull::SafeNonNull, FakeProcess, FakeState};
+    use super::*;
+
+    fn entry(visits: u32) -> (SafeNonNull<Node<FakeProcess>>, TableEntry) {
+        let node = SafeNonNull::new(Node::new(FakeState::new()));
+
+        for _ in 0..visits {
+            node.visit();
+        }
+
+        (node, TableEntry::new(node))
+    }
+
+    #[test]
+    fn collisions_follow_replacement() {
+        let (a, shallow) = entry(5);
+        let (b, deep) = entry(0);
+
+        for (replacement, keeps_shallow) in [(Replacement::Always, false), (Replacement::Depth, true), (Replacement::Visits, true)] {
+            let table = FixedTable::new(3, replacement);
+            assert_eq!(table.capacity(), 4);
+
+            table.insert(1, shallow, 1);
+            table.insert(5, deep, 2);
+
+            assert_eq!(table.len(), 1);
+            assert_eq!(table.get(1), if keeps_shallow { Some(shallow) } else { None });
+            assert_eq!(table.get(5), if keeps_shallow { None } else { Some(deep) });
+        }
+
+        a.drop();
+        b.drop();
+    }
+
+    #[test]
+    fn retain_and_clear() {
+        let (a, first) = entry(0);
+        let (b, second) = entry(0);
+        let mut table = FixedTable::new(4, Replacement::Always);
+
+        table.insert(0, first, 0);
+        table.insert(1, second, 1);
+        table.retain(&mut |entry| *entry == first);
+
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.get(0), Some(first));
+        assert_eq!(table.get(1), None);
+
+        table.clear();
+        assert!(table.is_empty());
+
+        a.drop();
+        b.drop();
+    }
+}
@@ -0,0 +1,84 @@
+mod dash;
+mod disabled;
+mod fixed;
+mod sharded;
+
+pub use self::dash::*;
+pub use self::disabled::*;
+pub use self::fixed::*;
+pub use self::sharded::*;
+
+use crate::{node::Node, process::Process, safe_nonnull::SafeNonNull};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// A node of a search tree as stored in a `TranspositionTable`. The table
+/// does not own the node, so an entry that is replaced or removed only stops
+/// the node from being shared by future transpositions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TableEntry {
+    node: *mut (),
+    visits: *const AtomicU32
+}
+
+unsafe impl Send for TableEntry {}
+unsafe impl Sync for TableEntry {}
+
+impl TableEntry {
+    pub(super) fn new<P: Process>(node: SafeNonNull<Node<P>>) -> Self {
+        Self { node: node.as_ptr() as *mut (), visits: node.visits_counter() }
+    }
+
+    pub(super) fn node<P: Process>(&self) -> SafeNonNull<Node<P>> {
+        SafeNonNull::from_raw(self.node as *mut Node<P>)
+    }
+
+    /// Returns the number of times the node of this entry has been updated.
+    pub fn visits(&self) -> u32 {
+        unsafe { &*self.visits }.load(Ordering::Relaxed)
+    }
+}
+
+/// A map from the hash of a state to the node of that state in a search tree,
+/// which is used to share nodes between transpositions. An implementation is
+/// free to forget entries, at the cost of fewer shared nodes.
+pub trait TranspositionTable: Send + Sync {
+    /// Returns the entry with the given `hash`, if there is one.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - the hash of the state to look for
+    ///
+    fn get(&self, hash: u128) -> Option<TableEntry>;
+
+    /// Insert the given `entry` with the given `hash`, which may replace an
+    /// existing entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash` - the hash of the state of the entry
+    /// * `entry` - the entry to insert
+    /// * `depth` - the distance from the root to the node of the entry
+    ///
+    fn insert(&self, hash: u128, entry: TableEntry, depth: u32);
+
+    /// Remove every entry for which `keep` returns false. The nodes of the
+    /// entries might already have been dropped, so `keep` should only compare
+    /// the entries themselves.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - returns if an entry should be kept
+    ///
+    fn retain(&mut self, keep: &mut dyn FnMut(&TableEntry) -> bool);
+
+    /// Remove every entry.
+    fn clear(&mut self);
+
+    /// Returns the number of entries.
+    fn len(&self) -> usize;
+
+    /// Returns if there are no entries.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
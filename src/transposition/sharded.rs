@@ -0,0 +1,131 @@
+use std::{collections::HashMap, hash::{BuildHasherDefault, Hasher}, sync::RwLock};
+use super::{TableEntry, TranspositionTable};
+
+/// A hasher that passes through the value it is given, since the keys of a
+/// transposition table are already hashes. A `u128` key is folded and then
+/// remixed, so that the high bits that choose the shard are not also the
+/// high bits that the hash map uses to tag its buckets.
+#[derive(Default)]
+struct IdentityHasher {
+    hash: u64
+}
+
+impl Hasher for IdentityHasher {
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.hash = self.hash.rotate_left(8) ^ byte as u64;
+        }
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.hash = fold(i).wrapping_mul(0x9e3779b97f4a7c15);
+    }
+}
+
+fn fold(hash: u128) -> u64 {
+    (hash >> 64) as u64 ^ hash as u64
+}
+
+type Shard = RwLock<HashMap<u128, TableEntry, BuildHasherDefault<IdentityHasher>>>;
+
+/// A transposition table that never forgets an entry, split into several
+/// open-addressing hash maps that use the hashes of the states without
+/// hashing them again. The hashes should be uniformly distributed, since the
+/// shard is chosen by their high bits.
+pub struct ShardedTable {
+    shards: Vec<Shard>,
+    shift: u32
+}
+
+impl Default for ShardedTable {
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+impl ShardedTable {
+    /// Returns an empty table with at least the given number of shards,
+    /// which is rounded up to a power of two.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_shards` - the minimum number of shards
+    ///
+    pub fn new(num_shards: usize) -> Self {
+        let num_shards = num_shards.max(1).next_power_of_two();
+        let shards = (0..num_shards).map(|_| Shard::default()).collect();
+        let shift = 64 - num_shards.trailing_zeros();
+
+        Self { shards, shift }
+    }
+
+    fn shard(&self, hash: u128) -> &Shard {
+        let index = fold(hash).checked_shr(self.shift).unwrap_or(0);
+
+        &self.shards[index as usize]
+    }
+}
+
+impl TranspositionTable for ShardedTable {
+    fn get(&self, hash: u128) -> Option<TableEntry> {
+        self.shard(hash).read().unwrap().get(&hash).copied()
+    }
+
+    fn insert(&self, hash: u128, entry: TableEntry, _: u32) {
+        self.shard(hash).write().unwrap().insert(hash, entry);
+    }
+
+    fn retain(&mut self, keep: &mut dyn FnMut(&TableEntry) -> bool) {
+        for shard in &mut self.shards {
+            shard.get_mut().unwrap().retain(|_, entry| keep(entry));
+        }
+    }
+
+    fn clear(&mut self) {
+        for shard in &mut self.shards {
+            shard.get_mut().unwrap().clear();
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.read().unwrap().len()).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_tags_differ_within_shard() {
+        let table = ShardedTable::new(128);
+        let tags = (0..64u128)
+            .inspect(|&hash| assert!(std::ptr::eq(table.shard(hash), &table.shards[0])))
+            .map(|hash| {
+                let mut hasher = IdentityHasher::default();
+                hasher.write_u128(hash);
+                hasher.finish() >> 57
+            })
+            .collect::<std::collections::HashSet<_>>();
+
+        assert!(tags.len() > 1);
+    }
+
+    #[test]
+    fn shards_by_high_bits() {
+        let table = ShardedTable::new(3);
+
+        assert_eq!(table.shards.len(), 4);
+        assert!(std::ptr::eq(table.shard(0), &table.shards[0]));
+        assert!(std::ptr::eq(table.shard(u64::MAX as u128), &table.shards[3]));
+        assert!(std::ptr::eq(table.shard(1 << 62), &table.shards[1]));
+
+        let table = ShardedTable::new(1);
+
+        assert!(std::ptr::eq(table.shard(u64::MAX as u128), &table.shards[0]));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::packed::{pack, unpack};
 use std::sync::atomic::{Ordering, AtomicU64};
 use super::update::Update;
 
@@ -5,19 +6,6 @@ pub struct PerChild {
     atomic_per_child: AtomicU64,
 }
 
-#[inline]
-fn pack(value: f32, total_visits: u32) -> u64 {
-    ((f32::to_bits(value) as u64) << 32) | total_visits as u64
-}
-
-#[inline]
-fn unpack(packed: u64) -> (f32, u32) {
-    let value = (packed >> 32) as u32;
-    let total_visits = (packed & 0xffffffff) as u32;
-
-    (f32::from_bits(value), total_visits)
-}
-
 impl Clone for PerChild {
     fn clone(&self) -> Self {
         Self {
@@ -50,6 +38,21 @@ impl PerChild {
         }).unwrap();
     }
 
+    /// Add the given total `value` of `visits` evaluations to this edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the sum of the values of all evaluations
+    /// * `visits` - the number of evaluations
+    ///
+    pub fn add(&self, value: f32, visits: u32) {
+        self.atomic_per_child.fetch_update(Ordering::AcqRel, Ordering::Acquire, |prev_value| {
+            let (prev_value, prev_visits) = unpack(prev_value);
+
+            Some(pack(prev_value + value, prev_visits + visits))
+        }).unwrap();
+    }
+
     #[inline]
     pub fn visits(&self) -> u32 {
         unpack(self.atomic_per_child.load(Ordering::Relaxed)).1
@@ -60,6 +63,23 @@ impl PerChild {
         unpack(self.atomic_per_child.load(Ordering::Relaxed)).0
     }
 
+    /// Returns the statistics of the same edge from several search trees
+    /// combined, with the sum of their visits and values.
+    ///
+    /// # Arguments
+    ///
+    /// * `per_children` - the statistics to combine
+    ///
+    pub fn merged<'a>(per_children: impl IntoIterator<Item=&'a Self>) -> Self {
+        let (value, visits) = per_children.into_iter().fold((0.0, 0), |(value, visits), per_child| {
+            let (other_value, other_visits) = unpack(per_child.atomic_per_child.load(Ordering::Relaxed));
+
+            (value + other_value, visits + other_visits)
+        });
+
+        Self { atomic_per_child: AtomicU64::new(pack(value, visits)) }
+    }
+
     #[inline(always)]
     pub fn win_rate(&self, total_value: f32, visits: u32) -> f32 {
         if visits > 0 {
@@ -76,6 +96,22 @@ impl PerChild {
 
         self.win_rate(value, visits) + (2.0f32 * ln_n / (visits + 1) as f32).sqrt()
     }
+
+    /// Returns the upper confidence bound of this edge, where the average
+    /// value is replaced by the given `value`, such as the value of the node
+    /// this edge leads to.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - the average value of this edge
+    /// * `total_visits` - the number of visits to the parent
+    ///
+    #[inline(always)]
+    pub fn uct_with_value(&self, value: f32, total_visits: u32) -> f32 {
+        let ln_n = (total_visits as f32).ln();
+
+        value + (2.0f32 * ln_n / (self.visits() + 1) as f32).sqrt()
+    }
 }
 
 #[cfg(test)]
@@ -83,10 +119,14 @@ mod tests {
     use super::*;
 
     #[test]
-    fn unpack_pack() {
-        let (value, visits) = unpack(pack(3.14, 7));
-
-        assert_eq!(value, 3.14);
-        assert_eq!(visits, 7);
+    fn merged_sums_visits_and_values() {
+        let per_children = [PerChild::new(), PerChild::new()];
+        per_children[0].update(&Update::new(1.0));
+        per_children[1].update(&Update::new(0.5));
+        per_children[1].update(&Update::new(0.0));
+
+        let merged = PerChild::merged(per_children.iter());
+        assert_eq!(merged.visits(), 3);
+        assert_eq!(merged.total_value(), 1.5);
     }
 }
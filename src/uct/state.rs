@@ -19,6 +19,11 @@ impl State {
         self.visits.fetch_add(1, Ordering::AcqRel);
     }
 
+    /// Add the given number of `visits` to this state.
+    pub fn add(&self, visits: u32) {
+        self.visits.fetch_add(visits, Ordering::AcqRel);
+    }
+
     pub fn baseline(total_visits: u32) -> f32 {
         (2.0 * (total_visits as f32).ln()).sqrt()
     }
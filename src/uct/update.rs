@@ -12,3 +12,9 @@ impl Update {
         self.value
     }
 }
+
+impl From<f32> for Update {
+    fn from(value: f32) -> Self {
+        Self::new(value)
+    }
+}
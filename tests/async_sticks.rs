@@ -0,0 +1,116 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+use futures::{channel::oneshot, executor::block_on, future};
+use mcts_rs::{game::{Game, GameProcess, GameState}, maxn, AsyncSearch, Mcts, Node, PendingPolicy, ProbeStatus, Trace};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{future::Future, sync::atomic::{AtomicUsize, Ordering}, task::Poll, thread};
+use self::common::Sticks;
+
+type Tree = Mcts<GameProcess<Sticks>>;
+
+fn new_tree() -> Tree {
+    Mcts::new(GameProcess::new().with_expand_threshold(1), GameState::new(Sticks::new(10)))
+}
+
+/// Returns the game at the end of the given `trace`, and whether a state
+/// should be added for it.
+fn next_game(trace: &Trace<'_, GameProcess<Sticks>, Node<GameProcess<Sticks>>>) -> Option<(Sticks, bool)> {
+    trace.steps().last().map(|last_step| last_step.map(|state, per_child| {
+        let mut game = state.game().clone();
+        game.play(per_child.mv());
+        (game, trace.is_expanded())
+    }))
+}
+
+/// Returns a future that is pending for the given number of polls, like a
+/// call to a slow service.
+fn delay(polls: usize) -> impl Future<Output=()> {
+    let mut remaining = polls;
+
+    future::poll_fn(move |cx| {
+        if remaining == 0 {
+            Poll::Ready(())
+        } else {
+            remaining -= 1;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    })
+}
+
+/// - The number of evaluations in flight is bounded by `max_pending`, and
+///   more than one evaluation is in flight at a time.
+#[test]
+fn pending_evaluations_are_bounded() {
+    let search_tree = new_tree();
+    let in_flight = AtomicUsize::new(0);
+    let max_in_flight = AtomicUsize::new(0);
+    let statuses = block_on(AsyncSearch::new(4).search(&search_tree, 500, next_game, |(game, is_expanded)| {
+        let in_flight = &in_flight;
+        let max_in_flight = &max_in_flight;
+        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        max_in_flight.fetch_max(current, Ordering::SeqCst);
+
+        async move {
+            delay(game.num_remaining as usize % 3 + 1).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            let update = maxn::Update::new((0..2).map(|player| game.reward(player)));
+
+            (is_expanded.then(|| GameState::new(game)), update)
+        }
+    }));
+
+    assert_eq!(statuses.len(), 500);
+    assert_eq!(in_flight.load(Ordering::SeqCst), 0);
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 4);
+}
+
+/// - Evaluations that run on other threads are backed up, and the search
+///   finds the winning move of leaving a multiple of three sticks.
+#[test]
+fn evaluations_on_other_threads() {
+    let search_tree = new_tree();
+    let statuses = block_on(AsyncSearch::new(8).search(&search_tree, 2000, next_game, |(game, is_expanded)| {
+        let (sender, receiver) = oneshot::channel();
+
+        thread::spawn(move || {
+            let mut rng = StdRng::seed_from_u64(game.num_remaining as u64);
+            let update = GameProcess::new().rollout(&game, &mut rng);
+
+            sender.send((is_expanded.then(|| GameState::new(game)), update)).ok();
+        });
+
+        async move { receiver.await.unwrap() }
+    }));
+
+    assert_eq!(statuses.len(), 2000);
+    assert_eq!(search_tree.path().next().unwrap().map(|_, per_child| per_child.mv()), 1);
+}
+
+/// - No more probes are made once the search tree has been stopped.
+#[test]
+fn stop_ends_the_search() {
+    let search_tree = new_tree();
+    search_tree.stop();
+
+    let statuses = block_on(AsyncSearch::new(8).search(&search_tree, 100, next_game, |(game, is_expanded)| {
+        future::ready((is_expanded.then(|| GameState::new(game)), maxn::Update::zero_sum(0, 0.5)))
+    }));
+
+    assert_eq!(statuses, vec! [ProbeStatus::Cancelled]);
+}
+
+/// - Waiting for pending edges is rejected, since it would block the search.
+#[test]
+#[should_panic(expected = "cannot wait for pending edges")]
+fn wait_policy_is_rejected() {
+    let mut search_tree = new_tree();
+    search_tree.set_pending_policy(PendingPolicy::Wait);
+
+    block_on(AsyncSearch::new(8).search(&search_tree, 100, next_game, |(game, is_expanded)| {
+        future::ready((is_expanded.then(|| GameState::new(game)), maxn::Update::zero_sum(0, 0.5)))
+    }));
+}
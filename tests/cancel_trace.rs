@@ -0,0 +1,193 @@
+use mcts_rs::{Mcts, PendingPolicy, PerChild, Process, ProbeStatus, SelectResult, State};
+use std::{sync::{atomic::{AtomicU32, Ordering}, Arc, Barrier}, thread};
+
+/// A single edge from the root to a terminal state, where selecting the edge
+/// adds a virtual loss that is removed by both an update and a cancel. If
+/// there is a `pause`, then selecting an existing edge waits on it twice.
+struct LineState {
+    is_terminal: bool
+}
+
+impl State for LineState {
+    fn hash(&self) -> Option<u64> {
+        None
+    }
+}
+
+#[derive(Default)]
+struct LinePerChild {
+    visits: AtomicU32,
+    virtual_losses: AtomicU32
+}
+
+impl PerChild for LinePerChild {
+    type Key = u32;
+
+    fn key(&self) -> Self::Key {
+        0
+    }
+}
+
+#[derive(Default)]
+struct LineProcess {
+    pause: Option<Arc<Barrier>>
+}
+
+impl Process for LineProcess {
+    type State = LineState;
+    type PerChild = LinePerChild;
+    type Update = ();
+
+    fn best<'a>(&self, _: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> Option<<Self::PerChild as PerChild>::Key> where Self::PerChild: 'a {
+        edges.map(|edge| edge.key()).next()
+    }
+
+    fn select<'a>(&self, state: &Self::State, mut edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        if state.is_terminal {
+            SelectResult::None
+        } else if let Some(edge) = edges.next() {
+            edge.virtual_losses.fetch_add(1, Ordering::Relaxed);
+
+            if let Some(pause) = &self.pause {
+                pause.wait();
+                pause.wait();
+            }
+
+            SelectResult::Existing(edge.key())
+        } else {
+            let per_child = LinePerChild::default();
+            per_child.virtual_losses.fetch_add(1, Ordering::Relaxed);
+            SelectResult::Add(per_child)
+        }
+    }
+
+    fn update(&self, _: &Self::State, per_child: &Self::PerChild, _: &Self::Update, _: bool) {
+        per_child.visits.fetch_add(1, Ordering::Relaxed);
+        per_child.virtual_losses.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    fn cancel(&self, _: &Self::State, per_child: &Self::PerChild) {
+        per_child.virtual_losses.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn root_edge(search_tree: &Mcts<LineProcess>) -> Option<(u32, u32)> {
+    search_tree.edges(1).first().map(|step| step.map(|_, per_child| {
+        (per_child.visits.load(Ordering::Relaxed), per_child.virtual_losses.load(Ordering::Relaxed))
+    }))
+}
+
+/// - Cancelling the trace that added an edge removes the edge, and the next
+///   probe adds it again.
+#[test]
+fn cancel_removes_expanded_edge() {
+    let search_tree = Mcts::new(LineProcess::default(), LineState { is_terminal: false });
+    let (trace, status) = search_tree.probe();
+
+    assert_eq!(status, ProbeStatus::Expanded);
+    search_tree.cancel(trace, true);
+    assert_eq!(root_edge(&search_tree), None);
+
+    let (trace, status) = search_tree.probe();
+
+    assert_eq!(status, ProbeStatus::Expanded);
+    search_tree.update(trace, Some(LineState { is_terminal: true }), ());
+    assert_eq!(root_edge(&search_tree), Some((1, 0)));
+}
+
+/// - Cancelling without removal keeps the edge, but rolls back the virtual
+///   loss.
+#[test]
+fn cancel_rolls_back_virtual_loss() {
+    let search_tree = Mcts::new(LineProcess::default(), LineState { is_terminal: false });
+    let (trace, _) = search_tree.probe();
+
+    assert_eq!(root_edge(&search_tree), Some((0, 1)));
+    search_tree.cancel(trace, false);
+    assert_eq!(root_edge(&search_tree), Some((0, 0)));
+}
+
+/// - An edge is not removed while another trace is waiting for it, and a
+///   dropped trace no longer counts as waiting.
+#[test]
+fn cancel_keeps_edge_with_other_pending_traces() {
+    let search_tree = Mcts::new(LineProcess::default(), LineState { is_terminal: false });
+    let (first, first_status) = search_tree.probe();
+    let (second, second_status) = search_tree.probe();
+
+    assert_eq!((first_status, second_status), (ProbeStatus::Expanded, ProbeStatus::Busy));
+    search_tree.cancel(first, true);
+    assert_eq!(root_edge(&search_tree), Some((0, 1)));
+
+    drop(second);
+
+    let (third, _) = search_tree.probe();
+    search_tree.update(third, Some(LineState { is_terminal: true }), ());
+    assert_eq!(root_edge(&search_tree), Some((1, 1)));
+}
+
+/// - A probe that races with a cancel that removes its edge selects again,
+///   so it never ends without its final step.
+#[test]
+fn probe_during_cancel_ends_at_pending_edge() {
+    let search_tree = Mcts::new(LineProcess::default(), LineState { is_terminal: false });
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..2000 {
+                    let (trace, status) = search_tree.probe();
+
+                    assert!(matches!(status, ProbeStatus::Expanded | ProbeStatus::Busy));
+                    assert_eq!(trace.steps().len(), 1);
+                    assert!(trace.is_pending());
+                    search_tree.cancel(trace, true);
+                }
+            });
+        }
+    });
+}
+
+/// - Cancelling the trace that probes joined rolls back the virtual loss of
+///   the joined probes as well, since their evaluation is never shared.
+#[test]
+fn cancel_rolls_back_joined_virtual_loss() {
+    let mut search_tree = Mcts::new(LineProcess::default(), LineState { is_terminal: false });
+    search_tree.set_pending_policy(PendingPolicy::Join);
+
+    let (first, first_status) = search_tree.probe();
+    let (_, second_status) = search_tree.probe();
+
+    assert_eq!((first_status, second_status), (ProbeStatus::Expanded, ProbeStatus::Joined));
+    assert_eq!(root_edge(&search_tree), Some((0, 2)));
+    search_tree.cancel(first, true);
+    assert_eq!(root_edge(&search_tree), Some((0, 0)));
+}
+
+/// - A probe that is stopped part way returns the steps it has taken, so
+///   cancelling it rolls back their virtual loss.
+#[test]
+fn stopped_probe_can_be_cancelled() {
+    let pause = Arc::new(Barrier::new(2));
+    let search_tree = Mcts::new(LineProcess { pause: Some(pause.clone()) }, LineState { is_terminal: false });
+    let (trace, _) = search_tree.probe();
+
+    search_tree.update(trace, Some(LineState { is_terminal: false }), ());
+
+    thread::scope(|s| {
+        let probe = s.spawn(|| search_tree.probe());
+
+        pause.wait();
+        search_tree.stop();
+        pause.wait();
+
+        let (trace, status) = probe.join().unwrap();
+
+        assert_eq!(status, ProbeStatus::Cancelled);
+        assert_eq!(trace.steps().len(), 1);
+        assert_eq!(root_edge(&search_tree), Some((1, 1)));
+        search_tree.cancel(trace, false);
+    });
+
+    assert_eq!(root_edge(&search_tree), Some((1, 0)));
+}
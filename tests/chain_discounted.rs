@@ -0,0 +1,87 @@
+use mcts_rs::{uct, Mcts, PendingPolicy, PerChild, Process, ProbeStatus, SelectResult, State};
+
+const LENGTH: u32 = 3;
+
+/// A chain of `LENGTH` transitions, where every transition has a reward of
+/// one.
+struct ChainState {
+    depth: u32
+}
+
+impl State for ChainState {
+    fn hash(&self) -> Option<u64> {
+        None
+    }
+}
+
+struct ChainPerChild {
+    uct: uct::PerChild
+}
+
+impl PerChild for ChainPerChild {
+    type Key = u32;
+
+    fn key(&self) -> Self::Key {
+        0
+    }
+}
+
+struct ChainProcess;
+
+impl Process for ChainProcess {
+    type State = ChainState;
+    type PerChild = ChainPerChild;
+    type Update = uct::Update;
+
+    fn best<'a>(&self, _: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> Option<<Self::PerChild as PerChild>::Key> where Self::PerChild: 'a {
+        edges.map(|edge| edge.key()).next()
+    }
+
+    fn select<'a>(&self, state: &Self::State, mut edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        if state.depth == LENGTH {
+            SelectResult::None
+        } else if let Some(edge) = edges.next() {
+            SelectResult::Existing(edge.key())
+        } else {
+            SelectResult::Add(ChainPerChild { uct: uct::PerChild::new() })
+        }
+    }
+
+    fn update(&self, _: &Self::State, per_child: &Self::PerChild, update: &Self::Update, _: bool) {
+        per_child.uct.update(update);
+    }
+}
+
+#[test]
+fn returns_are_discounted() {
+    let search_tree = Mcts::new(ChainProcess, ChainState { depth: 0 });
+
+    loop {
+        let (trace, status) = search_tree.probe();
+        let depth = trace.steps().len() as u32;
+        let rewards = vec! [1.0; depth as usize];
+
+        search_tree.update_discounted(trace, Some(ChainState { depth }), &rewards, 0.0, 0.5);
+
+        if status == ProbeStatus::Empty {
+            break
+        }
+    }
+
+    // the probes saw the root edge with a trace of length 1, 2, 3, and 3
+    let total_values = search_tree.path()
+        .map(|step| step.map(|_, per_child| (per_child.uct.visits(), per_child.uct.total_value())))
+        .collect::<Vec<_>>();
+
+    assert_eq!(total_values, vec! [(4, 1.0 + 1.5 + 1.75 + 1.75), (3, 1.0 + 1.5 + 1.5), (2, 2.0)]);
+}
+
+#[test]
+#[should_panic(expected = "joined probes")]
+fn join_is_rejected() {
+    let mut search_tree = Mcts::new(ChainProcess, ChainState { depth: 0 });
+    search_tree.set_pending_policy(PendingPolicy::Join);
+
+    let (trace, _) = search_tree.probe();
+    search_tree.update_discounted(trace, Some(ChainState { depth: 1 }), &[1.0], 0.0, 0.5);
+}
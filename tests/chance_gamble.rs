@@ -0,0 +1,182 @@
+use mcts_rs::{chance, uct, Mcts, PerChild, Process, SelectResult, State};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const SAFE: u8 = 0;
+const GAMBLE: u8 = 1;
+const WIN: u8 = 2;
+const LOSE: u8 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Decision,
+    Chance,
+    Terminal(f32)
+}
+
+/// A single decision between a safe reward of `0.6`, or a gamble that wins
+/// `1.0` or `0.0` with equal probability.
+struct GambleState {
+    kind: Kind,
+    visits: uct::State,
+    expectation: chance::Expectation
+}
+
+impl GambleState {
+    fn new(kind: Kind) -> Self {
+        Self { kind, visits: uct::State::new(), expectation: chance::Expectation::new() }
+    }
+
+    fn play(&self, key: u8) -> Self {
+        match key {
+            SAFE => Self::new(Kind::Terminal(0.6)),
+            GAMBLE => Self::new(Kind::Chance),
+            WIN => Self::new(Kind::Terminal(1.0)),
+            _ => Self::new(Kind::Terminal(0.0))
+        }
+    }
+}
+
+impl State for GambleState {
+    fn hash(&self) -> Option<u64> {
+        match self.kind {
+            Kind::Decision => Some(0),
+            Kind::Chance => Some(1),
+            Kind::Terminal(value) => Some(2 + value.to_bits() as u64)
+        }
+    }
+}
+
+struct GamblePerChild {
+    key: u8,
+    uct: uct::PerChild
+}
+
+impl GamblePerChild {
+    fn new(key: u8) -> Self {
+        Self { key, uct: uct::PerChild::new() }
+    }
+
+    fn mean(&self) -> f32 {
+        self.uct.total_value() / self.uct.visits().max(1) as f32
+    }
+}
+
+impl PerChild for GamblePerChild {
+    type Key = u8;
+
+    fn key(&self) -> Self::Key {
+        self.key
+    }
+}
+
+struct GambleProcess;
+
+impl Process for GambleProcess {
+    type State = GambleState;
+    type PerChild = GamblePerChild;
+    type Update = uct::Update;
+
+    fn best<'a>(&self, _: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> Option<<Self::PerChild as PerChild>::Key> where Self::PerChild: 'a {
+        edges.max_by_key(|edge| edge.uct.visits()).map(|edge| edge.key())
+    }
+
+    fn select<'a>(&self, state: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        if state.kind != Kind::Decision {
+            return SelectResult::None;
+        }
+
+        let total_visits = state.visits.visits();
+        let edges = edges.collect::<Vec<_>>();
+
+        if edges.len() < 2 {
+            SelectResult::Add(GamblePerChild::new(edges.len() as u8))
+        } else {
+            let best_edge = edges.into_iter()
+                .max_by(|a, b| a.uct.uct(total_visits).partial_cmp(&b.uct.uct(total_visits)).unwrap())
+                .unwrap();
+
+            SelectResult::Existing(best_edge.key())
+        }
+    }
+
+    fn outcomes(&self, state: &Self::State) -> Option<Vec<(Self::PerChild, f32)>> {
+        if state.kind == Kind::Chance {
+            Some(vec! [(GamblePerChild::new(WIN), 0.5), (GamblePerChild::new(LOSE), 0.5)])
+        } else {
+            None
+        }
+    }
+
+    fn update_chance<'a>(&self, state: &Self::State, outcomes: impl Iterator<Item=(&'a Self::PerChild, f32)>) where Self::PerChild: 'a {
+        if let Some(value) = chance::expectation(outcomes, |per_child| per_child.mean()) {
+            state.expectation.set(value);
+        }
+    }
+
+    fn update(&self, state: &Self::State, per_child: &Self::PerChild, update: &Self::Update, _: bool) {
+        state.visits.update();
+        per_child.uct.update(update);
+    }
+
+    fn update_with_child(&self, state: &Self::State, per_child: &Self::PerChild, update: &Self::Update, child: Option<&Self::State>) {
+        match child.and_then(|child| child.expectation.get()) {
+            Some(value) => self.update(state, per_child, &uct::Update::new(value), true),
+            None => self.update(state, per_child, update, child.is_some())
+        }
+    }
+}
+
+#[test]
+fn prefers_safe_over_gamble() {
+    let mut prng = StdRng::seed_from_u64(0xcafed00d);
+    let search_tree = Mcts::new(GambleProcess, GambleState::new(Kind::Decision));
+
+    for _ in 0..500 {
+        let (trace, _) = search_tree.probe();
+        let next_state = trace.steps().last().map(|step| step.map(|state, per_child| state.play(per_child.key())));
+
+        if let Some(next_state) = next_state {
+            let value = match next_state.kind {
+                Kind::Terminal(value) => value,
+                _ => prng.gen_range(0..2) as f32
+            };
+
+            search_tree.update(trace, Some(next_state), uct::Update::new(value));
+        }
+    }
+
+    let root_edges = search_tree.edges(2).iter()
+        .map(|step| step.map(|_, per_child| (per_child.key(), per_child.mean())))
+        .collect::<Vec<_>>();
+
+    assert_eq!(root_edges[0].0, SAFE);
+    assert_eq!(root_edges[1].0, GAMBLE);
+    assert!((0.4..0.6).contains(&root_edges[1].1), "{:?}", root_edges);
+}
+
+#[test]
+fn outcomes_are_sampled() {
+    let search_tree = Mcts::new(GambleProcess, GambleState::new(Kind::Chance));
+
+    for _ in 0..200 {
+        let (trace, _) = search_tree.probe();
+        let next_state = trace.steps().last().map(|step| step.map(|state, per_child| state.play(per_child.key())));
+
+        if let Some(next_state) = next_state {
+            let value = match next_state.kind {
+                Kind::Terminal(value) => value,
+                _ => unreachable!()
+            };
+
+            search_tree.update(trace, Some(next_state), uct::Update::new(value));
+        }
+    }
+
+    let visits = search_tree.edges(2).iter()
+        .map(|step| step.map(|_, per_child| (per_child.key(), per_child.uct.visits())))
+        .collect::<Vec<_>>();
+
+    assert_eq!(visits.len(), 2);
+    assert!(visits.iter().all(|&(_, visits)| (70..130).contains(&visits)), "{:?}", visits);
+    assert!(search_tree.root().expectation.get().map(|value| (0.4..0.6).contains(&value)).unwrap_or(false));
+}
@@ -0,0 +1,66 @@
+use mcts_rs::game::Game;
+
+/// Two players take turns to remove between one and `max_take` sticks,
+/// whoever removes the last stick wins. Most positions can be reached in
+/// several ways.
+#[derive(Clone)]
+pub struct Sticks {
+    pub num_remaining: u32,
+    pub turn: usize,
+    max_take: u32
+}
+
+impl Sticks {
+    /// Returns the game with `num_remaining` sticks, where the first player
+    /// is to move and every player removes one or two sticks.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_remaining` - the number of sticks left
+    ///
+    pub fn new(num_remaining: u32) -> Self {
+        Self { num_remaining, turn: 0, max_take: 2 }
+    }
+
+    /// Returns this game where the given `turn` player is to move.
+    #[allow(unused)]
+    pub fn with_turn(self, turn: usize) -> Self {
+        Self { turn, ..self }
+    }
+
+    /// Returns this game where every player removes at most `max_take`
+    /// sticks.
+    #[allow(unused)]
+    pub fn with_max_take(self, max_take: u32) -> Self {
+        Self { max_take, ..self }
+    }
+}
+
+impl Game for Sticks {
+    type Move = u32;
+
+    fn legal_moves(&self) -> Vec<u32> {
+        (1..=self.max_take).filter(|&n| n <= self.num_remaining).collect()
+    }
+
+    fn play(&mut self, mv: u32) {
+        self.num_remaining -= mv;
+        self.turn = 1 - self.turn;
+    }
+
+    fn current_player(&self) -> usize {
+        self.turn
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.num_remaining == 0
+    }
+
+    fn reward(&self, player: usize) -> f32 {
+        (self.turn != player) as i32 as f32
+    }
+
+    fn hash(&self) -> Option<u64> {
+        Some(2 * self.num_remaining as u64 + self.turn as u64)
+    }
+}
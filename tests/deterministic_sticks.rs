@@ -0,0 +1,104 @@
+mod common;
+
+use mcts_rs::{game::{Game, GameProcess, GameState}, simultaneous::{Policy, SimultaneousGame, SimultaneousProcess, SimultaneousState}, Deterministic, Mcts, PerChild};
+use self::common::Sticks;
+
+/// Both players show one side of a coin, the first player wins if they match.
+#[derive(Clone)]
+struct Pennies {
+    played: Option<(u8, u8)>
+}
+
+impl SimultaneousGame for Pennies {
+    type Move = u8;
+
+    fn legal_moves(&self, _: usize) -> Vec<u8> {
+        vec! [0, 1]
+    }
+
+    fn play(&mut self, joint_move: &[u8]) {
+        self.played = Some((joint_move[0], joint_move[1]));
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.played.is_some()
+    }
+
+    fn reward(&self, player: usize) -> f32 {
+        let (a, b) = self.played.unwrap();
+
+        ((a == b) == (player == 0)) as i32 as f32
+    }
+}
+
+fn search_sticks(seed: u64, num_threads: usize) -> Vec<(u32, u32, u32)> {
+    let search_tree = Mcts::new(GameProcess::new(), GameState::new(Sticks::new(10)));
+    let process = GameProcess::new();
+
+    Deterministic::new(seed).with_threads(num_threads).search(
+        &search_tree,
+        200,
+        |trace| {
+            trace.steps().last().map(|step| step.map(|state, per_child| {
+                let mut game = state.game().clone();
+                game.play(per_child.mv());
+                game
+            }))
+        },
+        |game, rng| {
+            let update = process.rollout(&game, rng);
+
+            (Some(GameState::new(game)), update)
+        }
+    );
+
+    search_tree.edges(usize::MAX).iter()
+        .map(|step| step.map(|_, per_child| (per_child.mv(), per_child.visits(), per_child.value().to_bits())))
+        .collect()
+}
+
+fn search_pennies(seed: u64, num_threads: usize) -> Vec<(u32, u32)> {
+    let search_tree = Mcts::new(
+        SimultaneousProcess::new().with_policy(Policy::Exp3 { gamma: 0.2 }),
+        SimultaneousState::new(Pennies { played: None })
+    );
+
+    Deterministic::new(seed).with_threads(num_threads).search(
+        &search_tree,
+        100,
+        |trace| {
+            trace.steps().last().map(|step| step.map(|state, per_child| {
+                let mut game = state.game().clone();
+                game.play(&state.joint_move(per_child.key()));
+                game
+            }))
+        },
+        |game, rng| {
+            let update = SimultaneousProcess::<Pennies>::new().rollout(&game, rng);
+
+            (None, update)
+        }
+    );
+
+    let root = search_tree.root();
+
+    (0..2).flat_map(|player| root.stats(player).iter().map(|stats| (stats.visits(), stats.value().to_bits())).collect::<Vec<_>>()).collect()
+}
+
+#[test]
+fn same_seed_same_tree() {
+    for num_threads in [1, 4] {
+        let first = search_sticks(0xcafed00d, num_threads);
+
+        assert_eq!(first.iter().map(|&(_, visits, _)| visits).sum::<u32>(), 200 * num_threads as u32);
+        assert_eq!(first, search_sticks(0xcafed00d, num_threads));
+    }
+}
+
+#[test]
+fn same_seed_same_sampled_selection() {
+    let first = search_pennies(0xcafed00d, 4);
+
+    assert_eq!(first, search_pennies(0xcafed00d, 4));
+    assert_ne!(first, search_pennies(0xdeadbeef, 4));
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use mcts_rs::{distributed::{merge_deltas, read_deltas, serve_tcp, Delta, Worker}, game::{search_once, GameProcess, GameState}, Mcts};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{env, io::{self, Write}, net::{TcpListener, TcpStream}, process::{Child, Command, Stdio}, thread, time::{Duration, Instant}};
+use self::common::Sticks;
+
+const ADDR_VAR: &str = "MCTS_DISTRIBUTED_COORDINATOR";
+const NUM_WORKERS: usize = 3;
+const NUM_PROBES: u32 = 600;
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+fn new_search_tree() -> Mcts<GameProcess<Sticks>> {
+    Mcts::new(GameProcess::new(), GameState::new(Sticks::new(7)))
+}
+
+/// The body of a worker process, which is this test binary started again by
+/// `coordinator_merges_workers` with the address of the coordinator in the
+/// environment. Does nothing when run as an ordinary test.
+#[test]
+fn worker_process() {
+    let addr = match env::var(ADDR_VAR) {
+        Ok(addr) => addr,
+        Err(_) => return
+    };
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let mut prng = StdRng::seed_from_u64(std::process::id() as u64);
+    let mut worker = Worker::new(2);
+    let search_tree = new_search_tree();
+
+    for i in 1..=NUM_PROBES {
+        search_once(&search_tree, &mut prng);
+
+        if i % 100 == 0 {
+            worker.sync(&search_tree, &mut stream).unwrap();
+        }
+    }
+}
+
+/// Returns if the given `worker` exits successfully before `deadline`, and
+/// kills it otherwise.
+fn wait_until(worker: &mut Child, deadline: Instant) -> bool {
+    loop {
+        if let Some(status) = worker.try_wait().unwrap() {
+            return status.success();
+        } else if Instant::now() >= deadline {
+            worker.kill().unwrap();
+            worker.wait().unwrap();
+
+            return false;
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// - The first player wins by taking one stick, leaving six.
+#[test]
+fn coordinator_merges_workers() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut workers = (0..NUM_WORKERS).map(|_| {
+        Command::new(env::current_exe().unwrap())
+            .args(["--exact", "worker_process", "--test-threads=1"])
+            .env(ADDR_VAR, addr.to_string())
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap()
+    }).collect::<Vec<_>>();
+
+    let search_tree = new_search_tree();
+    let is_success = thread::scope(|s| {
+        let watchdog = s.spawn(|| {
+            let deadline = Instant::now() + TIMEOUT;
+            let statuses = workers.iter_mut().map(|worker| wait_until(worker, deadline)).collect::<Vec<_>>();
+
+            // connect in place of every worker that never did, so that the
+            // coordinator does not wait for them forever
+            for _ in 0..NUM_WORKERS {
+                drop(TcpStream::connect(addr));
+            }
+
+            statuses.into_iter().all(|is_success| is_success)
+        });
+
+        serve_tcp(&search_tree, &listener, NUM_WORKERS).unwrap();
+        watchdog.join().unwrap()
+    });
+
+    assert!(is_success);
+
+    let root_visits = search_tree.edges(usize::MAX).iter()
+        .map(|step| step.map(|_, per_child| per_child.visits()))
+        .sum::<u32>();
+
+    assert_eq!(root_visits, NUM_WORKERS as u32 * NUM_PROBES);
+    assert_eq!(search_tree.root().visits(), NUM_WORKERS as u32 * NUM_PROBES);
+    assert_eq!(search_tree.path().next().map(|step| step.key()), Some(1));
+    assert!(search_tree.path().count() >= 2);
+}
+
+/// - A delta with a move that is not legal is dropped, but the valid part of
+///   its path and every other delta are merged.
+#[test]
+fn invalid_delta_is_dropped() {
+    let search_tree = new_search_tree();
+    let deltas = vec! [Delta::new(vec! [1, 9], 1.0, 1), Delta::new(vec! [2], 1.0, 1)];
+
+    assert_eq!(merge_deltas(&search_tree, &deltas), 1);
+
+    let root_edges = search_tree.edges(usize::MAX).iter()
+        .map(|step| step.map(|_, per_child| (per_child.mv(), per_child.visits())))
+        .collect::<Vec<_>>();
+
+    assert_eq!(root_edges, vec! [(2, 1), (1, 0)]);
+}
+
+/// A connection to the coordinator that is always broken.
+struct Broken;
+
+impl Write for Broken {
+    fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+        Err(io::ErrorKind::BrokenPipe.into())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// - A change that could not be sent is sent again by the next sync.
+#[test]
+fn failed_sync_is_sent_again() {
+    let mut prng = StdRng::seed_from_u64(0x5eed);
+    let mut worker = Worker::new(1);
+    let search_tree = new_search_tree();
+
+    for _ in 0..50 {
+        search_once(&search_tree, &mut prng);
+    }
+
+    assert!(worker.sync(&search_tree, &mut Broken).is_err());
+
+    let mut message = vec! [];
+    let num_sent = worker.sync(&search_tree, &mut message).unwrap();
+    let deltas = read_deltas::<u32>(&mut message.as_slice()).unwrap().unwrap();
+
+    assert_eq!(deltas.len(), num_sent);
+    assert_eq!(deltas.iter().map(|delta| delta.visits()).sum::<u32>(), 50);
+}
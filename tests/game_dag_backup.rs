@@ -0,0 +1,86 @@
+mod common;
+
+use mcts_rs::{game::{search_once, Backup, GamePerChild, GameProcess, GameState}, maxn, Mcts, Process};
+use rand::{rngs::StdRng, SeedableRng};
+use self::common::Sticks;
+
+fn search(backup: Backup) -> Mcts<GameProcess<Sticks>> {
+    let mut rng = StdRng::seed_from_u64(0xd06);
+    let process = GameProcess::new().with_expand_threshold(1).with_backup(backup);
+    let search_tree = Mcts::new(process, GameState::new(Sticks::new(13).with_max_take(3)));
+
+    for _ in 0..5000 {
+        search_once(&search_tree, &mut rng);
+    }
+
+    search_tree
+}
+
+/// - Both parents of a shared position see every evaluation that reached it,
+///   while each edge only counts the visits it routed itself.
+#[test]
+fn shared_child_value_is_seen_by_every_parent() {
+    let process = GameProcess::new().with_backup(Backup::Dag);
+    let parents = [GameState::new(Sticks::new(5).with_max_take(3)), GameState::new(Sticks::new(4).with_max_take(3))];
+    let edges = [GamePerChild::new(2), GamePerChild::new(1)];
+    let child = GameState::new(Sticks::new(3).with_max_take(3).with_turn(1));
+
+    process.update_with_child(&parents[0], &edges[0], &maxn::Update::zero_sum(0, 1.0), Some(&child));
+    process.update_with_child(&parents[0], &edges[0], &maxn::Update::zero_sum(0, 1.0), Some(&child));
+    process.update_with_child(&parents[1], &edges[1], &maxn::Update::zero_sum(0, 0.0), Some(&child));
+
+    assert_eq!(edges[0].visits(), 2);
+    assert_eq!(edges[1].visits(), 1);
+    assert_eq!(edges[0].value(), 1.0);
+    assert_eq!(edges[1].value(), 0.0);
+    assert_eq!(child.value(0), Some(2.0 / 3.0));
+    assert_eq!(edges[1].child_value(), Some(2.0 / 3.0));
+    assert!(edges[1].uct(3, Backup::Dag) > edges[1].uct(3, Backup::Tree));
+}
+
+/// - An edge sees the evaluations that reach its child through another
+///   parent after the edge itself was last updated.
+#[test]
+fn shared_child_value_follows_other_parents() {
+    let process = GameProcess::new().with_backup(Backup::Dag);
+    let parents = [GameState::new(Sticks::new(5).with_max_take(3)), GameState::new(Sticks::new(4).with_max_take(3))];
+    let edges = [GamePerChild::new(2), GamePerChild::new(1)];
+    let child = GameState::new(Sticks::new(3).with_max_take(3).with_turn(1));
+
+    process.update_with_child(&parents[1], &edges[1], &maxn::Update::zero_sum(0, 0.0), Some(&child));
+    assert_eq!(edges[1].child_value(), Some(0.0));
+
+    let before = edges[1].uct(2, Backup::Dag);
+    process.update_with_child(&parents[0], &edges[0], &maxn::Update::zero_sum(0, 1.0), Some(&child));
+
+    assert_eq!(edges[1].visits(), 1);
+    assert_eq!(edges[1].child_value(), Some(0.5));
+    assert!(edges[1].uct(2, Backup::Dag) > before);
+}
+
+/// - The tree backup never records the value of a child.
+#[test]
+fn tree_backup_ignores_children() {
+    let process = GameProcess::new();
+    let parent = GameState::new(Sticks::new(5).with_max_take(3));
+    let edge = GamePerChild::new(2);
+    let child = GameState::new(Sticks::new(3).with_max_take(3).with_turn(1));
+
+    process.update_with_child(&parent, &edge, &maxn::Update::zero_sum(0, 1.0), Some(&child));
+
+    assert_eq!(child.value(0), None);
+    assert_eq!(edge.child_value(), None);
+}
+
+/// - The first player wins by leaving a multiple of four sticks, with either
+///   backup.
+#[test]
+fn dag_backup_finds_the_best_move() {
+    for backup in [Backup::Tree, Backup::Dag] {
+        let search_tree = search(backup);
+        let (mv, child_value) = search_tree.path().next().unwrap().map(|_, per_child| (per_child.mv(), per_child.child_value()));
+
+        assert_eq!(mv, 1);
+        assert_eq!(child_value.is_some(), backup == Backup::Dag);
+    }
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use mcts_rs::{game::{search_once, Backup, GameProcess, GameState}, Ensemble, Mcts};
+use rand::{rngs::StdRng, SeedableRng};
+use self::common::Sticks;
+
+fn ensemble(num_trees: usize, backup: Backup) -> Ensemble<GameProcess<Sticks>> {
+    let game = Sticks::new(4);
+    let ensemble = Ensemble::new(
+        (0..num_trees).map(|_| Mcts::new(GameProcess::new().with_backup(backup), GameState::new(game.clone()))).collect()
+    );
+
+    ensemble.search(|i, search_tree| {
+        let mut prng = StdRng::seed_from_u64(0xcafed00d + i as u64);
+
+        for _ in 0..500 {
+            search_once(search_tree, &mut prng);
+        }
+    });
+
+    ensemble
+}
+
+fn root_visits(search_tree: &Mcts<GameProcess<Sticks>>) -> u32 {
+    search_tree.edges(usize::MAX).iter().map(|step| step.map(|_, per_child| per_child.visits())).sum()
+}
+
+/// - The first player wins by taking one stick, leaving three.
+#[test]
+fn merged_best_takes_one() {
+    let ensemble = ensemble(4, Backup::Tree);
+    let merged = ensemble.merged();
+
+    assert_eq!(ensemble.best(), Some(1));
+    assert_eq!(merged.iter().map(|per_child| per_child.visits()).sum::<u32>(), 2000);
+    assert_eq!(merged.iter().map(|per_child| per_child.mv()).collect::<Vec<_>>(), vec! [1, 2]);
+}
+
+#[test]
+fn into_tree_keeps_subtrees() {
+    let search_tree = ensemble(4, Backup::Tree).into_tree();
+
+    assert_eq!(root_visits(&search_tree), 2000);
+    assert_eq!(search_tree.path().map(|step| step.key()).next(), Some(1));
+    assert!(search_tree.path().count() >= 2);
+}
+
+/// - The root of the merged tree counts the visits of every search tree, and
+///   its edges keep reading the value of their child.
+#[test]
+fn into_tree_merges_root() {
+    let search_tree = ensemble(4, Backup::Dag).into_tree();
+    let child_values = search_tree.edges(usize::MAX).iter()
+        .map(|step| step.map(|_, per_child| per_child.child_value()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(search_tree.root().visits(), 2000);
+    assert!(child_values.iter().all(|child_value| child_value.is_some()));
+}
@@ -0,0 +1,78 @@
+use mcts_rs::{game::{search_once, Game, GameProcess, GameState}, Mcts};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Three players take turns to remove one or two sticks, whoever removes the
+/// last stick wins.
+#[derive(Clone)]
+struct LastStick {
+    num_remaining: u32,
+    turn: usize
+}
+
+impl Game for LastStick {
+    type Move = u32;
+
+    fn legal_moves(&self) -> Vec<u32> {
+        (1..=2).filter(|&n| n <= self.num_remaining).collect()
+    }
+
+    fn play(&mut self, mv: u32) {
+        self.num_remaining -= mv;
+        self.turn = (self.turn + 1) % 3;
+    }
+
+    fn num_players(&self) -> usize {
+        3
+    }
+
+    fn current_player(&self) -> usize {
+        self.turn
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.num_remaining == 0
+    }
+
+    fn reward(&self, player: usize) -> f32 {
+        ((self.turn + 2) % 3 == player) as i32 as f32
+    }
+
+    fn hash(&self) -> Option<u64> {
+        Some(3 * self.num_remaining as u64 + self.turn as u64)
+    }
+}
+
+fn search(game: LastStick) -> Mcts<GameProcess<LastStick>> {
+    let mut prng = StdRng::seed_from_u64(0xcafed00d);
+    let search_tree = Mcts::new(GameProcess::new(), GameState::new(game));
+
+    for _ in 0..1000 {
+        search_once(&search_tree, &mut prng);
+    }
+
+    search_tree
+}
+
+/// - The first player wins by taking both sticks.
+#[test]
+fn first_player_wins() {
+    let search_tree = search(LastStick { num_remaining: 2, turn: 0 });
+    let (key, value) = search_tree.path().next().unwrap().map(|_, per_child| (per_child.mv(), per_child.value()));
+
+    assert_eq!(key, 2);
+    assert!(value >= 0.98);
+}
+
+/// - The first player always loses, since the second player can take the
+///   remaining sticks whatever the first player does.
+#[test]
+fn second_player_wins() {
+    let search_tree = search(LastStick { num_remaining: 3, turn: 0 });
+    let values = search_tree.path()
+        .map(|step| step.map(|_, per_child| per_child.value()))
+        .collect::<Vec<_>>();
+
+    assert!(values.len() >= 2);
+    assert!(values[0] <= 0.02);
+    assert!(values[1] >= 0.98);
+}
@@ -0,0 +1,112 @@
+use mcts_rs::{game::{search_once, Game, GameProcess, GameState}, transposition::DisabledTable, Mcts, ProbeStatus};
+use rand::{rngs::StdRng, SeedableRng};
+
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2], [3, 4, 5], [6, 7, 8],
+    [0, 3, 6], [1, 4, 7], [2, 5, 8],
+    [0, 4, 8], [2, 4, 6]
+];
+
+#[derive(Clone)]
+struct TicTacToe {
+    cells: [u8; 9],
+    turn: usize
+}
+
+impl TicTacToe {
+    fn new(xs: &[usize], os: &[usize], turn: usize) -> Self {
+        let mut cells = [0; 9];
+        xs.iter().for_each(|&i| cells[i] = 1);
+        os.iter().for_each(|&i| cells[i] = 2);
+
+        Self { cells, turn }
+    }
+
+    fn winner(&self) -> Option<usize> {
+        LINES.iter()
+            .find(|line| self.cells[line[0]] != 0 && line.iter().all(|&i| self.cells[i] == self.cells[line[0]]))
+            .map(|line| self.cells[line[0]] as usize - 1)
+    }
+}
+
+impl Game for TicTacToe {
+    type Move = usize;
+
+    fn legal_moves(&self) -> Vec<usize> {
+        if self.winner().is_some() {
+            vec! []
+        } else {
+            (0..9).filter(|&i| self.cells[i] == 0).collect()
+        }
+    }
+
+    fn play(&mut self, mv: usize) {
+        self.cells[mv] = self.turn as u8 + 1;
+        self.turn = 1 - self.turn;
+    }
+
+    fn current_player(&self) -> usize {
+        self.turn
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.legal_moves().is_empty()
+    }
+
+    fn reward(&self, player: usize) -> f32 {
+        self.winner().map(|winner| (winner == player) as i32 as f32).unwrap_or(0.5)
+    }
+
+    fn hash(&self) -> Option<u64> {
+        Some(self.cells.iter().fold(self.turn as u64, |hash, &cell| 3 * hash + cell as u64))
+    }
+}
+
+/// ```
+/// X X .
+/// O O .
+/// . . .
+/// ```
+///
+/// - `X` wins by playing at `c1`.
+///
+#[test]
+fn x_wins() {
+    let mut prng = StdRng::seed_from_u64(0xcafed00d);
+    let search_tree = Mcts::new(GameProcess::new(), GameState::new(TicTacToe::new(&[0, 1], &[3, 4], 0)));
+
+    for _ in 0..1000 {
+        search_once(&search_tree, &mut prng);
+    }
+
+    let (key, value) = search_tree.path().next().unwrap().map(|_, per_child| (per_child.mv(), per_child.value()));
+    assert_eq!(key, 2);
+    assert!(value >= 0.98);
+}
+
+/// - A probe that ends at an edge that already has a child does not allocate
+///   a new node for it, so nothing is lost to races on a single thread.
+#[test]
+fn existing_children_are_not_reinserted() {
+    let mut prng = StdRng::seed_from_u64(0xcafed00d);
+    let search_tree = Mcts::new_with_table(GameProcess::new(), GameState::new(TicTacToe::new(&[0, 1], &[3, 4], 0)), DisabledTable);
+
+    for _ in 0..1000 {
+        search_once(&search_tree, &mut prng);
+    }
+
+    let metrics = search_tree.metrics();
+
+    assert!(metrics.empty > 0);
+    assert_eq!(metrics.lost_races, 0);
+    assert_eq!(metrics.nodes_allocated, metrics.transposition_misses + 1);
+}
+
+#[test]
+fn terminal_root_is_empty() {
+    let mut prng = StdRng::seed_from_u64(0xcafed00d);
+    let search_tree = Mcts::new(GameProcess::new(), GameState::new(TicTacToe::new(&[0, 1, 2], &[3, 4], 1)));
+
+    assert_eq!(search_once(&search_tree, &mut prng), ProbeStatus::Empty);
+    assert!(search_tree.path().next().is_none());
+}
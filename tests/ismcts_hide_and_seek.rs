@@ -0,0 +1,96 @@
+use mcts_rs::{game::Game, ismcts::{Determinize, MultipleObserver}};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const LEFT: u8 = 0;
+const RIGHT: u8 = 1;
+const PASS: u8 = 2;
+const SEEK_LEFT: u8 = 3;
+const SEEK_RIGHT: u8 = 4;
+const HIDDEN: u8 = 5;
+
+/// The first player hides to the left or to the right, without the second
+/// player seeing where. The second player then either passes for a reward of
+/// `0.6`, or seeks on one side for a reward of `1.0` if the first player is
+/// found there.
+#[derive(Clone)]
+struct HideAndSeek {
+    moves: Vec<u8>
+}
+
+impl Game for HideAndSeek {
+    type Move = u8;
+
+    fn legal_moves(&self) -> Vec<u8> {
+        match self.moves.len() {
+            0 => vec! [LEFT, RIGHT],
+            1 => vec! [PASS, SEEK_LEFT, SEEK_RIGHT],
+            _ => vec! []
+        }
+    }
+
+    fn play(&mut self, mv: u8) {
+        self.moves.push(mv);
+    }
+
+    fn current_player(&self) -> usize {
+        self.moves.len() % 2
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.moves.len() == 2
+    }
+
+    fn reward(&self, player: usize) -> f32 {
+        let seeker = match (self.moves[0], self.moves[1]) {
+            (_, PASS) => 0.6,
+            (LEFT, SEEK_LEFT) | (RIGHT, SEEK_RIGHT) => 1.0,
+            _ => 0.0
+        };
+
+        if player == 1 { seeker } else { 1.0 - seeker }
+    }
+}
+
+impl Determinize for HideAndSeek {
+    fn information_set(&self, observer: usize) -> u64 {
+        self.moves.iter().enumerate().fold(1, |hash, (i, &mv)| {
+            let mv = if i == 0 && observer == 1 { HIDDEN } else { mv };
+
+            7 * hash + mv as u64
+        })
+    }
+
+    fn determinize<R: Rng + ?Sized>(&self, observer: usize, rng: &mut R) -> Self {
+        let mut moves = self.moves.clone();
+
+        if observer == 1 && !moves.is_empty() {
+            moves[0] = rng.gen_range(LEFT..=RIGHT);
+        }
+
+        Self { moves }
+    }
+
+    fn observe(&self, mv: u8, observer: usize) -> u8 {
+        if self.moves.is_empty() && observer == 1 { HIDDEN } else { mv }
+    }
+}
+
+#[test]
+fn seeker_cannot_see_hider() {
+    let mut prng = StdRng::seed_from_u64(0xcafed00d);
+    let game = HideAndSeek { moves: vec! [] };
+    let search_trees = MultipleObserver::new(&game);
+
+    for _ in 0..4000 {
+        search_trees.search_once(&game, &mut prng);
+    }
+
+    let seeker_path = search_trees.search_tree(1).path().map(|step| step.key()).collect::<Vec<_>>();
+    assert_eq!(seeker_path, vec! [HIDDEN, PASS]);
+
+    let hider_values = search_trees.search_tree(0).edges(2).into_iter()
+        .map(|step| step.map(|_, per_child| per_child.value()))
+        .collect::<Vec<_>>();
+    assert_eq!(hider_values.len(), 2);
+    assert!(hider_values.iter().all(|&value| (0.3..0.55).contains(&value)), "{:?}", hider_values);
+}
@@ -0,0 +1,86 @@
+use mcts_rs::{game::Game, ismcts::{search_once, Determinize, InformationSet, IsmctsProcess}, Mcts};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const SAFE: u8 = 0;
+const GAMBLE: u8 = 1;
+const GUESS_EVEN: u8 = 2;
+const GUESS_ODD: u8 = 3;
+
+/// A single player either takes a safe reward of `0.6`, or gambles by
+/// guessing the parity of a hidden card. A search that can see the hidden
+/// card always wins the gamble, while a search over information sets knows
+/// that it only wins half of the time.
+#[derive(Clone)]
+struct Gamble {
+    card: u8,
+    moves: Vec<u8>
+}
+
+impl Game for Gamble {
+    type Move = u8;
+
+    fn legal_moves(&self) -> Vec<u8> {
+        match self.moves.as_slice() {
+            [] => vec! [SAFE, GAMBLE],
+            [GAMBLE] => vec! [GUESS_EVEN, GUESS_ODD],
+            _ => vec! []
+        }
+    }
+
+    fn play(&mut self, mv: u8) {
+        self.moves.push(mv);
+    }
+
+    fn current_player(&self) -> usize {
+        0
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.legal_moves().is_empty()
+    }
+
+    fn reward(&self, player: usize) -> f32 {
+        let reward = match self.moves.as_slice() {
+            [SAFE] => 0.6,
+            [GAMBLE, guess] => ((guess - GUESS_EVEN) == self.card % 2) as i32 as f32,
+            _ => 0.0
+        };
+
+        if player == 0 { reward } else { 1.0 - reward }
+    }
+}
+
+impl Determinize for Gamble {
+    fn information_set(&self, _: usize) -> u64 {
+        self.moves.iter().fold(1, |hash, &mv| 5 * hash + mv as u64)
+    }
+
+    fn determinize<R: Rng + ?Sized>(&self, _: usize, rng: &mut R) -> Self {
+        Self { card: rng.gen_range(0..10), moves: self.moves.clone() }
+    }
+}
+
+#[test]
+fn safe_is_better_than_guessing() {
+    let mut prng = StdRng::seed_from_u64(0xcafed00d);
+    let game = Gamble { card: 7, moves: vec! [] };
+    let search_tree = Mcts::new(IsmctsProcess::new(0), InformationSet::new(&game, 0));
+
+    for _ in 0..2000 {
+        search_once(&search_tree, &game, &mut prng);
+    }
+
+    let (mv, value) = search_tree.path().next().unwrap().map(|_, per_child| (per_child.mv(), per_child.value()));
+    assert_eq!(mv, SAFE);
+    assert!(value >= 0.55);
+
+    let gamble = search_tree.edges(2).into_iter()
+        .map(|step| step.map(|_, per_child| (per_child.mv(), per_child.value(), per_child.availability())))
+        .find(|&(mv, _, _)| mv == GAMBLE)
+        .unwrap();
+    assert!(gamble.1 < 0.6, "{:?}", gamble);
+
+    // every probe after the first, which added the safe move, had the gamble
+    // available
+    assert_eq!(gamble.2, 1999);
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use mcts_rs::{game::{search_once, GameProcess, GameState}, Mcts, Metrics};
+use rand::{rngs::StdRng, SeedableRng};
+use std::thread;
+use self::common::Sticks;
+
+#[test]
+fn single_thread_has_no_contention() {
+    let search_tree = Mcts::new(GameProcess::new(), GameState::new(Sticks::new(10)));
+    let mut rng = StdRng::seed_from_u64(0xbadc0ffe);
+
+    assert_eq!(search_tree.metrics(), Metrics { probes: 0, nodes_allocated: 1, ..Metrics::default() });
+
+    for _ in 0..500 {
+        search_once(&search_tree, &mut rng);
+    }
+
+    let metrics = search_tree.metrics();
+
+    assert_eq!(metrics.probes, 500);
+    assert_eq!(metrics.expand_retries, 0);
+    assert_eq!(metrics.lost_races, 0);
+    assert!(metrics.empty > 0);
+    assert!(metrics.transposition_hits > 0);
+    assert_eq!(metrics.nodes_allocated, search_tree.len() as u64);
+    assert_eq!(metrics.transposition_misses + 1, metrics.nodes_allocated);
+}
+
+#[test]
+fn terminal_root_is_empty() {
+    let search_tree = Mcts::new(GameProcess::new(), GameState::new(Sticks::new(0)));
+    let mut rng = StdRng::seed_from_u64(0xbadc0ffe);
+
+    for _ in 0..10 {
+        search_once(&search_tree, &mut rng);
+    }
+
+    assert_eq!(search_tree.metrics().probes, 10);
+    assert_eq!(search_tree.metrics().empty, 10);
+}
+
+#[test]
+fn lost_races_are_not_kept() {
+    let search_tree = Mcts::new(GameProcess::new(), GameState::new(Sticks::new(30)));
+
+    thread::scope(|scope| {
+        for seed in 0..4 {
+            let search_tree = &search_tree;
+
+            scope.spawn(move || {
+                let mut rng = StdRng::seed_from_u64(seed);
+
+                for _ in 0..500 {
+                    search_once(search_tree, &mut rng);
+                }
+            });
+        }
+    });
+
+    let metrics = search_tree.metrics();
+
+    assert_eq!(metrics.probes, 2000);
+    assert_eq!(metrics.nodes_allocated - metrics.lost_races, search_tree.len() as u64);
+}
@@ -0,0 +1,147 @@
+use mcts_rs::{uct, Mcts, OpenLoopProcess, PendingPolicy, PerChild, Process, ProbeStatus, SelectResult, State};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::sync::Mutex;
+
+const SAFE: u8 = 0;
+const RISKY: u8 = 1;
+const NUM_TURNS: u32 = 2;
+
+/// Every turn the player either gains one coin, or flips a coin to either
+/// gain three coins or nothing.
+#[derive(Clone)]
+struct Coins {
+    total: u32,
+    turn: u32
+}
+
+impl Coins {
+    fn value(&self) -> f32 {
+        self.total as f32 / (3 * NUM_TURNS) as f32
+    }
+}
+
+struct CoinsState {
+    visits: uct::State
+}
+
+impl State for CoinsState {
+    fn hash(&self) -> Option<u64> {
+        None
+    }
+}
+
+struct CoinsPerChild {
+    key: u8,
+    uct: uct::PerChild
+}
+
+impl PerChild for CoinsPerChild {
+    type Key = u8;
+
+    fn key(&self) -> Self::Key {
+        self.key
+    }
+}
+
+struct CoinsProcess {
+    prng: Mutex<StdRng>
+}
+
+impl Process for CoinsProcess {
+    type State = CoinsState;
+    type PerChild = CoinsPerChild;
+    type Update = uct::Update;
+
+    fn best<'a>(&self, _: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> Option<<Self::PerChild as PerChild>::Key> where Self::PerChild: 'a {
+        edges.max_by_key(|edge| edge.uct.visits()).map(|edge| edge.key())
+    }
+
+    fn select<'a>(&self, _: &Self::State, _: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        unreachable!()
+    }
+
+    fn update(&self, state: &Self::State, per_child: &Self::PerChild, update: &Self::Update, _: bool) {
+        state.visits.update();
+        per_child.uct.update(update);
+    }
+}
+
+impl OpenLoopProcess for CoinsProcess {
+    type Simulator = Coins;
+
+    fn new_state(&self, _: &Coins) -> Self::State {
+        CoinsState { visits: uct::State::new() }
+    }
+
+    fn step(&self, coins: &Coins, key: u8) -> Coins {
+        let gain = match key {
+            SAFE => 1,
+            _ => 3 * self.prng.lock().unwrap().gen_range(0..2)
+        };
+
+        Coins { total: coins.total + gain, turn: coins.turn + 1 }
+    }
+
+    fn select_open_loop<'a>(&self, state: &Self::State, coins: &Coins, edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        if coins.turn == NUM_TURNS {
+            return SelectResult::None;
+        }
+
+        let total_visits = state.visits.visits();
+        let edges = edges.collect::<Vec<_>>();
+
+        if edges.len() < 2 {
+            SelectResult::Add(CoinsPerChild { key: edges.len() as u8, uct: uct::PerChild::new() })
+        } else {
+            let best_edge = edges.into_iter()
+                .max_by(|a, b| a.uct.uct(total_visits).total_cmp(&b.uct.uct(total_visits)))
+                .unwrap();
+
+            SelectResult::Existing(best_edge.key())
+        }
+    }
+}
+
+#[test]
+fn risky_is_better_on_average() {
+    let process = CoinsProcess { prng: Mutex::new(StdRng::seed_from_u64(0xcafed00d)) };
+    let root = Coins { total: 0, turn: 0 };
+    let search_tree = Mcts::new_open_loop(process, &root);
+
+    for _ in 0..2000 {
+        let (trace, _, mut coins) = search_tree.probe_open_loop(&root);
+
+        if !trace.is_empty() {
+            while coins.turn < NUM_TURNS {
+                let key = search_tree.process().prng.lock().unwrap().gen_range(0..2);
+                coins = search_tree.process().step(&coins, key);
+            }
+
+            search_tree.update_open_loop(trace, &coins, uct::Update::new(coins.value()));
+        }
+    }
+
+    let path = search_tree.path().map(|step| step.key()).collect::<Vec<_>>();
+    assert_eq!(path, vec! [RISKY, RISKY]);
+
+    // the risky edge leads to a single node, regardless of the outcome of
+    // the coin flip
+    assert_eq!(search_tree.edges(3).len(), 2);
+    assert!(search_tree.path().nth(1).map(|step| step.map(|_, per_child| per_child.uct.visits() > 1000)).unwrap_or(false));
+}
+
+#[test]
+fn joined_probe_returns_root_simulator() {
+    let process = CoinsProcess { prng: Mutex::new(StdRng::seed_from_u64(0xcafed00d)) };
+    let root = Coins { total: 0, turn: 0 };
+    let mut search_tree = Mcts::new_open_loop(process, &root);
+    search_tree.set_pending_policy(PendingPolicy::Join);
+
+    let (_safe, _, _) = search_tree.probe_open_loop(&root);
+    let (_risky, _, _) = search_tree.probe_open_loop(&root);
+    let (trace, status, coins) = search_tree.probe_open_loop(&root);
+
+    assert_eq!(status, ProbeStatus::Joined);
+    assert!(trace.is_empty());
+    assert_eq!((coins.total, coins.turn), (0, 0));
+}
@@ -0,0 +1,242 @@
+mod common;
+
+use mcts_rs::{game::{GameProcess, GameState}, Mcts, PendingPolicy, PerChild, Process, ProbeStatus, SelectResult, State};
+use std::{sync::atomic::{AtomicU32, Ordering}, thread, time::Duration};
+use self::common::Sticks;
+
+/// A root with `num_edges` edges that are added in order, followed by the
+/// least visited edge, where every child is a terminal state.
+struct FanState {
+    is_terminal: bool,
+    adds: AtomicU32
+}
+
+impl FanState {
+    fn new(is_terminal: bool) -> Self {
+        Self { is_terminal, adds: AtomicU32::new(0) }
+    }
+}
+
+impl State for FanState {
+    fn hash(&self) -> Option<u64> {
+        None
+    }
+}
+
+struct FanPerChild {
+    key: u32,
+    visits: AtomicU32
+}
+
+impl PerChild for FanPerChild {
+    type Key = u32;
+
+    fn key(&self) -> Self::Key {
+        self.key
+    }
+}
+
+struct FanProcess {
+    num_edges: u32
+}
+
+impl Process for FanProcess {
+    type State = FanState;
+    type PerChild = FanPerChild;
+    type Update = ();
+
+    fn best<'a>(&self, _: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> Option<<Self::PerChild as PerChild>::Key> where Self::PerChild: 'a {
+        edges.map(|edge| edge.key()).next()
+    }
+
+    fn select<'a>(&self, state: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        if state.is_terminal {
+            return SelectResult::None;
+        }
+
+        let key = state.adds.fetch_add(1, Ordering::Relaxed);
+
+        if key < self.num_edges {
+            SelectResult::Add(FanPerChild { key, visits: AtomicU32::new(0) })
+        } else {
+            edges.min_by_key(|edge| edge.visits.load(Ordering::Relaxed))
+                .map(|edge| SelectResult::Existing(edge.key()))
+                .unwrap_or(SelectResult::None)
+        }
+    }
+
+    fn update(&self, _: &Self::State, per_child: &Self::PerChild, _: &Self::Update, _: bool) {
+        per_child.visits.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+fn search_tree(num_edges: u32, pending_policy: PendingPolicy) -> Mcts<FanProcess> {
+    let mut search_tree = Mcts::new(FanProcess { num_edges }, FanState::new(false));
+    search_tree.set_pending_policy(pending_policy);
+    search_tree
+}
+
+fn visits(search_tree: &Mcts<FanProcess>) -> Vec<u32> {
+    search_tree.edges(2).iter().map(|step| step.map(|_, per_child| per_child.visits.load(Ordering::Relaxed))).collect()
+}
+
+/// - By default a probe that reaches a pending edge ends at it.
+#[test]
+fn busy_ends_at_pending_edge() {
+    let search_tree = search_tree(1, PendingPolicy::default());
+    let (_first, first_status) = search_tree.probe();
+    let (second, second_status) = search_tree.probe();
+
+    assert_eq!(search_tree.pending_policy(), PendingPolicy::Busy);
+    assert_eq!((first_status, second_status), (ProbeStatus::Expanded, ProbeStatus::Busy));
+    assert_eq!(second.steps().iter().map(|step| step.key()).collect::<Vec<_>>(), vec! [0]);
+}
+
+/// - A probe that waits for a pending edge continues through the child that
+///   is added by the update of the other trace.
+#[test]
+fn wait_continues_through_new_child() {
+    let search_tree = search_tree(1, PendingPolicy::Wait);
+    let (first, _) = search_tree.probe();
+
+    thread::scope(|s| {
+        let waiting = s.spawn(|| {
+            let (trace, status) = search_tree.probe();
+
+            (trace.steps().len(), status)
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        search_tree.update(first, Some(FanState::new(true)), ());
+
+        assert_eq!(waiting.join().unwrap(), (1, ProbeStatus::Empty));
+    });
+
+    assert_eq!(search_tree.metrics().waits, 1);
+    assert_eq!(search_tree.metrics().busy, 0);
+}
+
+/// - A probe that waits for a pending edge ends at it if the other trace is
+///   updated without a child.
+#[test]
+fn wait_ends_at_edge_without_child() {
+    let search_tree = search_tree(1, PendingPolicy::Wait);
+    let (first, _) = search_tree.probe();
+
+    thread::scope(|s| {
+        let waiting = s.spawn(|| {
+            let (trace, status) = search_tree.probe();
+
+            (trace.steps().len(), status)
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        search_tree.update(first, None, ());
+
+        assert_eq!(waiting.join().unwrap(), (1, ProbeStatus::Busy));
+    });
+}
+
+/// - A game does not add a move again while its edge is pending, but adds the
+///   next unexplored move instead, and only ends at a pending edge once every
+///   move has been explored.
+#[test]
+fn skip_adds_next_unexplored_move() {
+    let mut search_tree = Mcts::new(GameProcess::new(), GameState::new(Sticks::new(5)));
+    search_tree.set_pending_policy(PendingPolicy::Skip);
+
+    let probes = (0..3).map(|_| search_tree.probe()).collect::<Vec<_>>();
+    let summary = probes.iter()
+        .map(|(trace, status)| (trace.steps().iter().map(|step| step.key()).collect::<Vec<_>>(), *status))
+        .collect::<Vec<_>>();
+
+    assert_eq!(summary[0], (vec! [1], ProbeStatus::Expanded));
+    assert_eq!(summary[1], (vec! [2], ProbeStatus::Expanded));
+    assert_eq!(summary[2].1, ProbeStatus::Busy);
+}
+
+/// - A pending edge is hidden from `select`, so the next best edge is taken.
+#[test]
+fn skip_selects_next_best_edge() {
+    let search_tree = search_tree(2, PendingPolicy::Skip);
+    let (_first, _) = search_tree.probe();
+    let (second, _) = search_tree.probe();
+
+    search_tree.update(second, Some(FanState::new(true)), ());
+    assert_eq!(visits(&search_tree), vec! [0, 1]);
+
+    let (trace, status) = search_tree.probe();
+
+    assert_eq!(status, ProbeStatus::Empty);
+    assert_eq!(trace.steps().iter().map(|step| step.key()).collect::<Vec<_>>(), vec! [1]);
+}
+
+/// - Without skipping, the same search picks the pending edge.
+#[test]
+fn busy_selects_pending_edge() {
+    let search_tree = search_tree(2, PendingPolicy::Busy);
+    let (_first, _) = search_tree.probe();
+    let (second, _) = search_tree.probe();
+
+    search_tree.update(second, Some(FanState::new(true)), ());
+
+    let (trace, status) = search_tree.probe();
+
+    assert_eq!(status, ProbeStatus::Busy);
+    assert_eq!(trace.steps().iter().map(|step| step.key()).collect::<Vec<_>>(), vec! [0]);
+}
+
+/// - A probe that joins a pending edge returns an empty trace, and is updated
+///   together with the trace it joined.
+#[test]
+fn join_shares_pending_update() {
+    let search_tree = search_tree(1, PendingPolicy::Join);
+    let (first, _) = search_tree.probe();
+    let (second, second_status) = search_tree.probe();
+
+    assert_eq!(second_status, ProbeStatus::Joined);
+    assert!(second.is_empty());
+    assert_eq!(visits(&search_tree), vec! [0]);
+
+    search_tree.update(first, Some(FanState::new(true)), ());
+
+    assert_eq!(visits(&search_tree), vec! [2]);
+    assert_eq!(search_tree.metrics().joins, 1);
+}
+
+/// - A probe that joined a trace that was dropped is discarded with it, so
+///   the next trace that evaluates the same edge is only updated itself.
+#[test]
+fn join_is_cleared_with_dropped_trace() {
+    let search_tree = search_tree(1, PendingPolicy::Join);
+    let (first, _) = search_tree.probe();
+    let (_, second_status) = search_tree.probe();
+
+    assert_eq!(second_status, ProbeStatus::Joined);
+    drop(first);
+
+    let (third, third_status) = search_tree.probe();
+
+    assert_eq!(third_status, ProbeStatus::Busy);
+    search_tree.update(third, None, ());
+    assert_eq!(visits(&search_tree), vec! [1]);
+}
+
+/// - Cancelling a trace does not remove the edge it added if another probe
+///   joined it, and the joined probe is discarded with the last mark.
+#[test]
+fn cancel_keeps_joined_edge() {
+    let search_tree = search_tree(1, PendingPolicy::Join);
+    let (first, first_status) = search_tree.probe();
+    let (_, second_status) = search_tree.probe();
+
+    assert_eq!((first_status, second_status), (ProbeStatus::Expanded, ProbeStatus::Joined));
+    search_tree.cancel(first, true);
+    assert_eq!(visits(&search_tree), vec! [0]);
+
+    let (third, third_status) = search_tree.probe();
+
+    assert_eq!(third_status, ProbeStatus::Busy);
+    search_tree.update(third, None, ());
+    assert_eq!(visits(&search_tree), vec! [1]);
+}
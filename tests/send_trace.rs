@@ -0,0 +1,52 @@
+mod common;
+
+use mcts_rs::{game::{Game, GameProcess, GameState}, Mcts, Node, ProbeStatus, Trace};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{sync::mpsc, thread};
+use self::common::Sticks;
+
+/// - Traces probed on one thread can be evaluated and backed up on another,
+///   and the search still finds the winning move of leaving a multiple of
+///   three sticks.
+#[test]
+fn probe_and_update_on_different_threads() {
+    let process = GameProcess::new().with_expand_threshold(1);
+    let search_tree = Mcts::new(process, GameState::new(Sticks::new(10)));
+    let (sender, receiver) = mpsc::sync_channel::<(Trace<'_, GameProcess<Sticks>, Node<GameProcess<Sticks>>>, ProbeStatus)>(8);
+
+    thread::scope(|scope| {
+        let search_tree = &search_tree;
+
+        scope.spawn(move || {
+            let mut rng = StdRng::seed_from_u64(0x5e4d);
+
+            for (trace, status) in receiver {
+                let game = trace.steps().last().map(|last_step| last_step.map(|state, per_child| {
+                    let mut game = state.game().clone();
+                    game.play(per_child.mv());
+                    game
+                }));
+
+                if let Some(game) = game {
+                    let update = search_tree.process().rollout(&game, &mut rng);
+                    let new_state = (status == ProbeStatus::Expanded).then(|| GameState::new(game));
+
+                    search_tree.update(trace, new_state, update);
+                }
+            }
+        });
+
+        for _ in 0..2000 {
+            let (trace, status) = search_tree.probe();
+
+            if !trace.is_empty() {
+                sender.send((trace, status)).unwrap();
+            }
+        }
+
+        drop(sender);
+    });
+
+    assert_eq!(search_tree.probes(), 2000);
+    assert_eq!(search_tree.path().next().unwrap().map(|_, per_child| per_child.mv()), 1);
+}
@@ -0,0 +1,73 @@
+use mcts_rs::{simultaneous::{search_once, Policy, SimultaneousGame, SimultaneousProcess, SimultaneousState}, Mcts};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// A single round of a two player matrix game, where `payoffs[a][b]` is the
+/// reward of the first player when the players choose `a` and `b`.
+#[derive(Clone)]
+struct Matrix {
+    payoffs: [[f32; 2]; 2],
+    played: Option<(u8, u8)>
+}
+
+impl SimultaneousGame for Matrix {
+    type Move = u8;
+
+    fn legal_moves(&self, _: usize) -> Vec<u8> {
+        vec! [0, 1]
+    }
+
+    fn play(&mut self, joint_move: &[u8]) {
+        self.played = Some((joint_move[0], joint_move[1]));
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.played.is_some()
+    }
+
+    fn reward(&self, player: usize) -> f32 {
+        let (a, b) = self.played.unwrap();
+        let reward = self.payoffs[a as usize][b as usize];
+
+        if player == 0 { reward } else { 1.0 - reward }
+    }
+}
+
+fn search(payoffs: [[f32; 2]; 2], policy: Policy) -> Mcts<SimultaneousProcess<Matrix>> {
+    let mut prng = StdRng::seed_from_u64(0xcafed00d);
+    let search_tree = Mcts::new(
+        SimultaneousProcess::new().with_policy(policy),
+        SimultaneousState::new(Matrix { payoffs, played: None })
+    );
+
+    for _ in 0..2000 {
+        search_once(&search_tree, &mut prng);
+    }
+
+    search_tree
+}
+
+/// The first player should always choose `1`, and the second player should
+/// then choose `0` to lose as little as possible.
+#[test]
+fn duct_finds_saddle_point() {
+    let search_tree = search([[0.2, 0.4], [0.6, 0.8]], Policy::Duct);
+    let root = search_tree.root();
+
+    assert_eq!(root.best_move(0), Some(1));
+    assert_eq!(root.best_move(1), Some(0));
+    assert_eq!(search_tree.path().next().map(|step| root.joint_move(step.key()).to_vec()), Some(vec! [1, 0]));
+}
+
+/// In matching pennies the only equilibrium is for both players to choose
+/// uniformly at random.
+#[test]
+fn exp3_mixes_matching_pennies() {
+    let search_tree = search([[1.0, 0.0], [0.0, 1.0]], Policy::Exp3 { gamma: 0.2 });
+    let root = search_tree.root();
+
+    for player in 0..2 {
+        let visits = root.stats(player).iter().map(|stats| stats.visits()).collect::<Vec<_>>();
+
+        assert!(visits.iter().all(|&visits| (600..1400).contains(&visits)), "{:?}", visits);
+    }
+}
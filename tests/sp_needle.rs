@@ -0,0 +1,127 @@
+use mcts_rs::{sp, Mcts, PerChild, Process, ProbeStatus, SelectResult, State};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+const DEPTH: usize = 3;
+
+/// A sequence of binary choices, where every sequence that starts with `0`
+/// has a reward of `0.6`, and every sequence that starts with `1` has a
+/// reward of `0.0` except for the needle `[1, 1, 1]` with a reward of `1.0`.
+struct NeedleState {
+    moves: Vec<u8>,
+    visits: AtomicU32
+}
+
+impl NeedleState {
+    fn new(moves: Vec<u8>) -> Self {
+        Self { moves, visits: AtomicU32::new(0) }
+    }
+
+    fn reward(moves: &[u8]) -> f32 {
+        match moves {
+            [0, ..] => 0.6,
+            [1, 1, 1] => 1.0,
+            _ => 0.0
+        }
+    }
+
+    fn rollout(&self, rng: &mut impl Rng) -> f32 {
+        let mut moves = self.moves.clone();
+
+        while moves.len() < DEPTH {
+            moves.push(rng.gen_range(0..2));
+        }
+
+        Self::reward(&moves)
+    }
+}
+
+impl State for NeedleState {
+    fn hash(&self) -> Option<u64> {
+        None
+    }
+}
+
+struct NeedlePerChild {
+    mv: u8,
+    sp: sp::PerChild
+}
+
+impl PerChild for NeedlePerChild {
+    type Key = u8;
+
+    fn key(&self) -> Self::Key {
+        self.mv
+    }
+}
+
+struct NeedleProcess;
+
+impl Process for NeedleProcess {
+    type State = NeedleState;
+    type PerChild = NeedlePerChild;
+    type Update = sp::Update;
+
+    fn best<'a>(&self, _: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> Option<<Self::PerChild as PerChild>::Key> where Self::PerChild: 'a {
+        sp::best(edges, |edge| (&edge.sp, edge.mv))
+    }
+
+    fn select<'a>(&self, state: &Self::State, edges: impl Iterator<Item=&'a Self::PerChild>) -> SelectResult<Self::PerChild> where Self::PerChild: 'a {
+        if state.moves.len() == DEPTH {
+            return SelectResult::None;
+        }
+
+        let total_visits = state.visits.load(Ordering::Relaxed);
+        let edges = edges.collect::<Vec<_>>();
+
+        if edges.len() < 2 {
+            let mv = (0..2).find(|mv| edges.iter().all(|edge| edge.mv != *mv)).unwrap();
+
+            SelectResult::Add(NeedlePerChild { mv, sp: sp::PerChild::new() })
+        } else {
+            let best = edges.iter().max_by(|a, b| {
+                a.sp.score(total_visits, 0.5, 1.0).total_cmp(&b.sp.score(total_visits, 0.5, 1.0))
+            });
+
+            SelectResult::Existing(best.unwrap().mv)
+        }
+    }
+
+    fn update(&self, state: &Self::State, per_child: &Self::PerChild, update: &Self::Update, _: bool) {
+        state.visits.fetch_add(1, Ordering::Relaxed);
+        per_child.sp.update(update);
+    }
+}
+
+/// - The path through the search tree follows the best reward seen, even
+///   though the other first move has the better mean reward, and the
+///   complete sequences are updated as terminal traces.
+#[test]
+fn path_follows_the_needle() {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    let search_tree = Mcts::new(NeedleProcess, NeedleState::new(vec! []));
+    let mut num_terminal = 0;
+
+    for _ in 0..500 {
+        let (trace, status) = search_tree.probe();
+        let moves = match trace.steps().last() {
+            Some(last_step) => last_step.map(|state, per_child| [state.moves.as_slice(), &[per_child.mv]].concat()),
+            None => break
+        };
+        let new_state = NeedleState::new(moves);
+        let reward = new_state.rollout(&mut rng);
+
+        if status == ProbeStatus::Empty {
+            num_terminal += 1;
+        }
+
+        search_tree.update(trace, (status == ProbeStatus::Expanded).then_some(new_state), sp::Update::new(reward));
+    }
+
+    let path = search_tree.path().map(|step| step.key()).collect::<Vec<_>>();
+    let best_max = search_tree.path().last().unwrap().map(|_, per_child| per_child.sp.max());
+
+    assert!(num_terminal > 0);
+    assert_eq!(path, vec! [1, 1, 1]);
+    assert_eq!(best_max, Some(1.0));
+}
@@ -1,8 +1,43 @@
-use rand::{rngs::StdRng, SeedableRng};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use mcts_rs::{Mcts, ProbeStatus};
 
 use super::*;
 
+pub fn search_once(search_tree: &Mcts<TicTacToeProcess>, prng: &mut impl Rng) -> ProbeStatus {
+    match search_tree.probe() {
+        (trace, ProbeStatus::Empty) if trace.is_empty() => { panic!() },
+        (trace, ProbeStatus::Cancelled) => {
+            search_tree.cancel(trace, false);
+            ProbeStatus::Cancelled
+        },
+        (trace, status) => {
+            let last_step = trace.steps().last().unwrap();
+            let (new_state, is_expandable) = last_step.map(|state, per_child| {
+                let mut board = state.board().clone();
+                board.place(per_child.vertex(), state.turn());
+
+                (
+                    TicTacToeState::new(board, -state.turn()),
+                    state.visits() >= 8 && !state.is_terminal()
+                )
+            });
+            let update = TicTacToeUpdate::new(
+                new_state.evaluate(prng),
+                new_state.turn()
+            );
+
+            if is_expandable {
+                search_tree.update(trace, Some(new_state), update);
+            } else {
+                search_tree.update(trace, None, update);
+            }
+
+            status
+        }
+    }
+}
+
+#[allow(unused)]
 pub fn assert_search(
     process: TicTacToeProcess,
     starting_point: TicTacToeState,
@@ -13,31 +48,7 @@ pub fn assert_search(
     let search_tree = Mcts::new(process, starting_point);
 
     while search_tree.root().visits() < 200 || !until(&search_tree) {
-        match search_tree.probe() {
-            (trace, ProbeStatus::Empty) if trace.is_empty() => { panic!() },
-            (trace, _) => {
-                let last_step = trace.steps().last().unwrap();
-                let (new_state, is_expandable) = last_step.map(|state, per_child| {
-                    let mut board = state.board().clone();
-                    board.place(per_child.vertex(), state.turn());
-
-                    (
-                        TicTacToeState::new(board, -state.turn()),
-                        state.visits() >= 8 && !state.is_terminal()
-                    )
-                });
-                let update = TicTacToeUpdate::new(
-                    new_state.evaluate(&mut prng),
-                    new_state.turn()
-                );
-
-                if is_expandable {
-                    search_tree.update(trace, Some(new_state), update);
-                } else {
-                    search_tree.update(trace, None, update);
-                }
-            }
-        }
+        search_once(&search_tree, &mut prng);
     }
 
     search_tree
@@ -23,6 +23,7 @@ impl TicTacToePerChild {
         }
     }
 
+    #[allow(unused)]
     pub fn value(&self) -> f32 {
         self.uct.win_rate(self.uct.total_value(), self.uct.visits())
     }
@@ -0,0 +1,89 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use mcts_rs::{game::{self, GameProcess, GameState}, Mcts};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{collections::HashMap, fmt, sync::{atomic::{AtomicU64, Ordering}, Mutex}};
+use tracing::{field::{Field, Visit}, span, Event, Metadata, Subscriber};
+use self::common::Sticks;
+
+struct Message(String);
+
+impl Visit for Message {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{:?}", value);
+        }
+    }
+}
+
+/// Counts every span by name, and every event by message.
+#[derive(Default)]
+struct Counter {
+    next_id: AtomicU64,
+    counts: Mutex<HashMap<String, usize>>
+}
+
+impl Counter {
+    fn count(&self, name: &str) -> usize {
+        self.counts.lock().unwrap().get(name).copied().unwrap_or(0)
+    }
+}
+
+impl Subscriber for &'static Counter {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        *self.counts.lock().unwrap().entry(span.metadata().name().to_string()).or_insert(0) += 1;
+
+        span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {
+        // pass
+    }
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {
+        // pass
+    }
+
+    fn event(&self, event: &Event<'_>) {
+        let mut message = Message(String::new());
+        event.record(&mut message);
+
+        *self.counts.lock().unwrap().entry(message.0).or_insert(0) += 1;
+    }
+
+    fn enter(&self, _: &span::Id) {
+        // pass
+    }
+
+    fn exit(&self, _: &span::Id) {
+        // pass
+    }
+}
+
+#[test]
+fn probes_inserts_and_updates_are_traced() {
+    let counter: &'static Counter = Box::leak(Box::default());
+    let search_tree = Mcts::new(GameProcess::new(), GameState::new(Sticks::new(6)));
+    let mut rng = StdRng::seed_from_u64(42);
+
+    tracing::subscriber::with_default(counter, || {
+        for _ in 0..100 {
+            game::search_once(&search_tree, &mut rng);
+        }
+    });
+
+    let inserts = counter.count("insert new node") + counter.count("insert transposition hit") + counter.count("insert lost race");
+
+    assert_eq!(counter.count("probe"), 100);
+    assert_eq!(counter.count("probe finished"), 100);
+    assert_eq!(counter.count("insert lost race"), 0);
+    assert!(counter.count("insert transposition hit") > 0);
+    assert!(inserts > 0 && inserts <= counter.count("update"));
+    assert_eq!(counter.count("insert new node"), search_tree.len() - 1);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use mcts_rs::{game::{search_once, Game, GameProcess, GameState}, Mcts};
+use rand::{rngs::StdRng, SeedableRng};
+use self::common::Sticks;
+
+/// The game of `Sticks`, where the 64-bit hash deliberately collides for
+/// positions with the same number of remaining sticks modulo four, while the
+/// 128-bit hash is only used if `wide` is set.
+#[derive(Clone)]
+struct Colliding {
+    sticks: Sticks,
+    wide: bool
+}
+
+impl Game for Colliding {
+    type Move = u32;
+
+    fn legal_moves(&self) -> Vec<u32> {
+        self.sticks.legal_moves()
+    }
+
+    fn play(&mut self, mv: u32) {
+        self.sticks.play(mv);
+    }
+
+    fn current_player(&self) -> usize {
+        self.sticks.current_player()
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.sticks.is_terminal()
+    }
+
+    fn reward(&self, player: usize) -> f32 {
+        self.sticks.reward(player)
+    }
+
+    fn hash(&self) -> Option<u64> {
+        Some(2 * (self.sticks.num_remaining % 4) as u64 + self.sticks.turn as u64)
+    }
+
+    fn hash128(&self) -> Option<u128> {
+        if self.wide {
+            Some((self.sticks.num_remaining as u128) << 64 | self.sticks.turn as u128)
+        } else {
+            self.hash().map(u128::from)
+        }
+    }
+
+    fn same_as(&self, other: &Self) -> bool {
+        self.sticks.num_remaining == other.sticks.num_remaining && self.sticks.turn == other.sticks.turn
+    }
+}
+
+fn search(wide: bool) -> Mcts<GameProcess<Colliding>> {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    let search_tree = Mcts::new(GameProcess::new(), GameState::new(Colliding { sticks: Sticks::new(10), wide }));
+
+    for _ in 0..2000 {
+        search_once(&search_tree, &mut rng);
+    }
+
+    search_tree
+}
+
+/// - Colliding positions get their own node, and the first player still
+///   finds the winning move of leaving a multiple of three sticks.
+#[test]
+fn collisions_are_verified() {
+    let search_tree = search(false);
+    let metrics = search_tree.metrics();
+
+    assert!(metrics.transposition_collisions > 0);
+    assert!(metrics.transposition_misses >= metrics.transposition_collisions);
+    assert_eq!(search_tree.path().next().unwrap().map(|_, per_child| per_child.mv()), 1);
+}
+
+/// - Distinct 128-bit hashes never collide, but transpositions are still
+///   shared.
+#[test]
+fn wide_hashes_do_not_collide() {
+    let search_tree = search(true);
+    let metrics = search_tree.metrics();
+
+    assert_eq!(metrics.transposition_collisions, 0);
+    assert!(metrics.transposition_hits > 0);
+    assert_eq!(metrics.nodes_allocated, search_tree.len() as u64);
+    assert_eq!(search_tree.path().next().unwrap().map(|_, per_child| per_child.mv()), 1);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use mcts_rs::{game::{search_once, GameProcess, GameState}, transposition::{DashTable, DisabledTable, FixedTable, Replacement, ShardedTable, TranspositionTable}, Mcts};
+use rand::{rngs::StdRng, SeedableRng};
+use std::thread;
+use self::common::Sticks;
+
+fn search(table: impl TranspositionTable + 'static) -> Mcts<GameProcess<Sticks>> {
+    let search_tree = Mcts::new_with_table(GameProcess::new(), GameState::new(Sticks::new(10)), table);
+
+    thread::scope(|scope| {
+        for seed in 0..4 {
+            let search_tree = &search_tree;
+
+            scope.spawn(move || {
+                let mut rng = StdRng::seed_from_u64(seed);
+
+                for _ in 0..1000 {
+                    search_once(search_tree, &mut rng);
+                }
+            });
+        }
+    });
+
+    search_tree
+}
+
+fn best_move(search_tree: &Mcts<GameProcess<Sticks>>) -> u32 {
+    search_tree.path().next().unwrap().map(|_, per_child| per_child.mv())
+}
+
+/// - Every table finds the winning move of leaving a multiple of three
+///   sticks.
+#[test]
+fn every_table_finds_the_best_move() {
+    assert_eq!(best_move(&search(DashTable::new())), 1);
+    assert_eq!(best_move(&search(ShardedTable::new(8))), 1);
+    assert_eq!(best_move(&search(FixedTable::new(4, Replacement::Depth))), 1);
+    assert_eq!(best_move(&search(FixedTable::new(4, Replacement::Visits))), 1);
+    assert_eq!(best_move(&search(DisabledTable)), 1);
+}
+
+/// - Only the unbounded tables remember every unique position, and the
+///   disabled table shares no nodes at all.
+#[test]
+fn tables_bound_their_entries() {
+    let dash = search(DashTable::new());
+    let sharded = search(ShardedTable::new(8));
+    let fixed = search(FixedTable::new(4, Replacement::Always));
+    let disabled = search(DisabledTable);
+
+    assert!(dash.len() > 4);
+    assert_eq!(sharded.len(), dash.len());
+    assert!(fixed.len() <= 4);
+    assert_eq!(disabled.len(), 0);
+    assert_eq!(disabled.metrics().transposition_hits, 0);
+    assert!(disabled.metrics().nodes_allocated > dash.metrics().nodes_allocated);
+}
@@ -0,0 +1,47 @@
+mod tic_tac_toe;
+
+use mcts_rs::{Mcts, ProbeStatus};
+use rand::thread_rng;
+use std::{sync::Arc, thread, time::Duration};
+
+#[test]
+fn stop_cancels_all_threads() {
+    let search_tree = Arc::new(Mcts::new(
+        tic_tac_toe::TicTacToeProcess::new(),
+        tic_tac_toe::TicTacToeState::starting_point()
+    ));
+    let handles = (0..4).map(|_| {
+        let search_tree = search_tree.clone();
+
+        thread::spawn(move || {
+            while tic_tac_toe::search_once(&search_tree, &mut thread_rng()) != ProbeStatus::Cancelled {
+                // pass
+            }
+        })
+    }).collect::<Vec<_>>();
+
+    thread::sleep(Duration::from_millis(10));
+    search_tree.stop();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let (trace, status) = search_tree.probe();
+    assert!(trace.is_empty());
+    assert_eq!(status, ProbeStatus::Cancelled);
+}
+
+#[test]
+fn resume_allows_search_again() {
+    let search_tree = Mcts::new(
+        tic_tac_toe::TicTacToeProcess::new(),
+        tic_tac_toe::TicTacToeState::starting_point()
+    );
+
+    search_tree.stop();
+    assert_eq!(tic_tac_toe::search_once(&search_tree, &mut thread_rng()), ProbeStatus::Cancelled);
+    search_tree.resume();
+    assert_eq!(tic_tac_toe::search_once(&search_tree, &mut thread_rng()), ProbeStatus::Expanded);
+    assert_eq!(search_tree.root().visits(), 1);
+}
@@ -0,0 +1,98 @@
+mod tic_tac_toe;
+
+use mcts_rs::{Mcts, Ponder};
+use rand::thread_rng;
+use std::{thread, time::{Duration, Instant}};
+
+fn after_center() -> tic_tac_toe::TicTacToeState {
+    let mut board = tic_tac_toe::TicTacToe::empty();
+    board.place(4, 1);
+
+    tic_tac_toe::TicTacToeState::new(board, -1)
+}
+
+/// ```
+/// . . .
+/// . X .
+/// . . .
+/// ```
+///
+/// - The opponent plays in the center, which has been searched.
+///
+#[test]
+fn ponder_hit_keeps_subtree() {
+    let ponder = Ponder::start(
+        Mcts::new(tic_tac_toe::TicTacToeProcess::new(), tic_tac_toe::TicTacToeState::starting_point()),
+        2,
+        |search_tree| { tic_tac_toe::search_once(search_tree, &mut thread_rng()); }
+    );
+
+    let deadline = Instant::now() + Duration::from_secs(60);
+
+    while ponder.search_tree().root().visits() < 2000 {
+        assert!(Instant::now() < deadline, "the ponder did not search");
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    let mut search_tree = ponder.play(4, after_center());
+
+    assert!(search_tree.root().visits() > 0);
+    assert!(search_tree.len() > 1);
+    assert_eq!(search_tree.root().board().hash(), after_center().board().hash());
+
+    let best_key = search_tree.path().next().unwrap().key();
+    assert!(search_tree.reroot(best_key));
+}
+
+/// ```
+/// . . .
+/// . X .
+/// . . .
+/// ```
+///
+/// - The opponent plays in the center, which has not been searched.
+///
+#[test]
+fn ponder_miss_discards_tree() {
+    let ponder = Ponder::start(
+        Mcts::new(tic_tac_toe::TicTacToeProcess::new(), tic_tac_toe::TicTacToeState::starting_point()),
+        0,
+        |search_tree| { tic_tac_toe::search_once(search_tree, &mut thread_rng()); }
+    );
+    let search_tree = ponder.play(4, after_center());
+
+    assert_eq!(search_tree.root().visits(), 0);
+    assert_eq!(search_tree.root().board().hash(), after_center().board().hash());
+    assert_eq!(search_tree.len(), 1);
+}
+
+/// - A stop asked for by the caller while pondering is kept after the ponder
+///   stops.
+#[test]
+fn ponder_keeps_stop_of_caller() {
+    let ponder = Ponder::start(
+        Mcts::new(tic_tac_toe::TicTacToeProcess::new(), tic_tac_toe::TicTacToeState::starting_point()),
+        2,
+        |search_tree| { tic_tac_toe::search_once(search_tree, &mut thread_rng()); }
+    );
+
+    ponder.search_tree().stop();
+
+    assert!(ponder.stop().is_stopped());
+}
+
+/// - A search tree that is stopped before pondering is left stopped, and
+///   one that is not is resumed.
+#[test]
+fn ponder_restores_stop() {
+    let search_tree = Mcts::new(tic_tac_toe::TicTacToeProcess::new(), tic_tac_toe::TicTacToeState::starting_point());
+    search_tree.stop();
+
+    let search_tree = Ponder::start(search_tree, 1, |_| {}).stop();
+    assert!(search_tree.is_stopped());
+
+    search_tree.resume();
+
+    let search_tree = Ponder::start(search_tree, 1, |_| {}).stop();
+    assert!(!search_tree.is_stopped());
+}